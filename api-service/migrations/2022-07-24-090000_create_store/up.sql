@@ -0,0 +1,12 @@
+-- Your SQL goes here
+
+CREATE EXTENSION IF NOT EXISTS postgis;
+
+CREATE TABLE store (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR(128) NOT NULL,
+  address VARCHAR(256),
+  location GEOGRAPHY(POINT, 4326) NOT NULL
+);
+
+CREATE INDEX store_location_idx ON store USING GIST (location);
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE item_price (
+  id SERIAL PRIMARY KEY,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  store_id INTEGER NOT NULL REFERENCES store(id) ON DELETE CASCADE,
+  reporter_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  price_cents INTEGER NOT NULL CHECK (price_cents >= 0),
+  currency VARCHAR(3) NOT NULL,
+  quantity DOUBLE PRECISION NOT NULL CHECK (quantity > 0),
+  unit VARCHAR(32) NOT NULL,
+  observed_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX item_price_item_store_idx ON item_price (item_id, store_id, observed_at DESC);
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+
+CREATE TABLE item_barcode (
+  gtin VARCHAR(14) PRIMARY KEY,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE
+);
+
+CREATE INDEX item_barcode_item_idx ON item_barcode (item_id);
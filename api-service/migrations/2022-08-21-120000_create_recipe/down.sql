@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE recipe_ingredient;
+DROP TABLE recipe;
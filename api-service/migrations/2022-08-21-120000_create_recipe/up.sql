@@ -0,0 +1,20 @@
+-- Your SQL goes here
+
+CREATE TABLE recipe (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR(128) NOT NULL,
+  description VARCHAR(512),
+  servings INTEGER NOT NULL CHECK (servings > 0),
+  steps TEXT[] NOT NULL DEFAULT '{}',
+  created_by INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE TABLE recipe_ingredient (
+  id SERIAL PRIMARY KEY,
+  recipe_id INTEGER NOT NULL REFERENCES recipe(id) ON DELETE CASCADE,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  quantity DOUBLE PRECISION NOT NULL CHECK (quantity > 0),
+  unit VARCHAR(32) NOT NULL
+);
+
+CREATE INDEX recipe_ingredient_recipe_idx ON recipe_ingredient (recipe_id);
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+
+CREATE TABLE meal_plan_entry (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  planned_for DATE NOT NULL,
+  meal_slot VARCHAR(32) NOT NULL,
+  recipe_id INTEGER NOT NULL REFERENCES recipe(id) ON DELETE CASCADE,
+  servings INTEGER NOT NULL CHECK (servings > 0)
+);
+
+CREATE INDEX meal_plan_entry_user_date_idx ON meal_plan_entry (user_id, planned_for);
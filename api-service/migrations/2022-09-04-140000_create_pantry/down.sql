@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE pantry_threshold;
+DROP TABLE pantry_item;
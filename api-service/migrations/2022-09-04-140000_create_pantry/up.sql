@@ -0,0 +1,21 @@
+-- Your SQL goes here
+
+CREATE TABLE pantry_item (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  quantity DOUBLE PRECISION NOT NULL CHECK (quantity > 0),
+  unit VARCHAR(32) NOT NULL,
+  location VARCHAR(32) NOT NULL,
+  expiry_date DATE
+);
+
+CREATE INDEX pantry_item_user_item_idx ON pantry_item (user_id, item_id);
+
+CREATE TABLE pantry_threshold (
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  quantity DOUBLE PRECISION NOT NULL CHECK (quantity > 0),
+  unit VARCHAR(32) NOT NULL,
+  PRIMARY KEY (user_id, item_id)
+);
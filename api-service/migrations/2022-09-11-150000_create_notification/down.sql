@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX pantry_item_expiry_idx;
+DROP TABLE notification;
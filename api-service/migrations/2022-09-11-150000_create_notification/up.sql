@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE notification (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  kind VARCHAR(32) NOT NULL,
+  message VARCHAR(512) NOT NULL,
+  pantry_item_id INTEGER REFERENCES pantry_item(id) ON DELETE CASCADE,
+  is_read BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  UNIQUE (pantry_item_id, kind)
+);
+
+CREATE INDEX notification_user_idx ON notification (user_id, created_at DESC);
+CREATE INDEX pantry_item_expiry_idx ON pantry_item (expiry_date);
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+
+CREATE TABLE list_template (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR(128) NOT NULL,
+  created_by INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE TABLE list_template_item (
+  id SERIAL PRIMARY KEY,
+  template_id INTEGER NOT NULL REFERENCES list_template(id) ON DELETE CASCADE,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  quantity DOUBLE PRECISION NOT NULL CHECK (quantity > 0),
+  unit VARCHAR(32) NOT NULL
+);
+
+CREATE INDEX list_template_item_template_idx ON list_template_item (template_id);
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+
+CREATE TABLE item_alias (
+  id SERIAL PRIMARY KEY,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  alias VARCHAR(128) NOT NULL
+);
+
+CREATE UNIQUE INDEX item_alias_alias_idx ON item_alias (LOWER(alias));
+CREATE INDEX item_alias_item_idx ON item_alias (item_id);
+
+CREATE TABLE item_translation (
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  locale VARCHAR(16) NOT NULL,
+  name VARCHAR(128) NOT NULL,
+  PRIMARY KEY (item_id, locale)
+);
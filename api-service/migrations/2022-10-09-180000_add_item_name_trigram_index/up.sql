@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX shopping_item_name_trgm_idx ON shopping_item USING GIN (LOWER(name) gin_trgm_ops);
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+
+CREATE TABLE item_proposal (
+  id SERIAL PRIMARY KEY,
+  proposed_by INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  item_id INTEGER REFERENCES shopping_item(id) ON DELETE CASCADE,
+  name VARCHAR(128) NOT NULL,
+  description VARCHAR(512),
+  image_url VARCHAR(256),
+  default_unit_type VARCHAR(32) NOT NULL,
+  status VARCHAR(32) NOT NULL DEFAULT 'pending',
+  rejection_reason VARCHAR(512),
+  resolved_item_id INTEGER REFERENCES shopping_item(id) ON DELETE SET NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  reviewed_at TIMESTAMP
+);
+
+CREATE INDEX item_proposal_status_idx ON item_proposal (status, created_at);
+CREATE INDEX item_proposal_proposed_by_idx ON item_proposal (proposed_by);
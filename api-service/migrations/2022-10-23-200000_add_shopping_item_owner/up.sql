@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+ALTER TABLE shopping_item ADD COLUMN owner_id INTEGER REFERENCES users(id) ON DELETE CASCADE;
+
+CREATE INDEX shopping_item_owner_idx ON shopping_item (owner_id);
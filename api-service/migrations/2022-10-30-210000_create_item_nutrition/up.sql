@@ -0,0 +1,16 @@
+-- Your SQL goes here
+
+CREATE TABLE item_nutrition (
+  item_id INTEGER PRIMARY KEY REFERENCES shopping_item(id) ON DELETE CASCADE,
+  energy_kcal DOUBLE PRECISION CHECK (energy_kcal >= 0),
+  protein_g DOUBLE PRECISION CHECK (protein_g >= 0),
+  fat_g DOUBLE PRECISION CHECK (fat_g >= 0),
+  carbohydrate_g DOUBLE PRECISION CHECK (carbohydrate_g >= 0),
+  sugar_g DOUBLE PRECISION CHECK (sugar_g >= 0),
+  salt_g DOUBLE PRECISION CHECK (salt_g >= 0),
+  fibre_g DOUBLE PRECISION CHECK (fibre_g >= 0),
+  unit_weight_g DOUBLE PRECISION CHECK (unit_weight_g > 0),
+  vegan BOOLEAN,
+  gluten_free BOOLEAN,
+  contains_nuts BOOLEAN
+);
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+CREATE TABLE user_dietary_restriction (
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  restriction VARCHAR(32) NOT NULL,
+  PRIMARY KEY (user_id, restriction)
+);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE item_price DROP COLUMN variant_id;
+ALTER TABLE item_barcode DROP COLUMN variant_id;
+DROP TABLE item_variant;
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE item_variant (
+  id SERIAL PRIMARY KEY,
+  item_id INTEGER NOT NULL REFERENCES shopping_item(id) ON DELETE CASCADE,
+  brand VARCHAR(128) NOT NULL,
+  package_quantity DOUBLE PRECISION NOT NULL CHECK (package_quantity > 0),
+  package_unit VARCHAR(32) NOT NULL,
+  image_url VARCHAR(256)
+);
+
+CREATE INDEX item_variant_item_idx ON item_variant (item_id);
+
+ALTER TABLE item_barcode ADD COLUMN variant_id INTEGER REFERENCES item_variant(id) ON DELETE SET NULL;
+ALTER TABLE item_price ADD COLUMN variant_id INTEGER REFERENCES item_variant(id) ON DELETE SET NULL;
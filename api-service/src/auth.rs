@@ -177,6 +177,46 @@ impl<'a, 'r> FromRequest<'a, 'r> for UserRequest<'a> {
     }
 }
 
+/// A request that may come from a signed-in user or an anonymous caller. An invalid
+/// token is still rejected rather than treated as anonymous.
+pub struct OptionalUserRequest<'a> {
+    pub state: StateInstance<'a>,
+    pub token: Option<AccessJwtToken>,
+}
+
+impl<'a> OptionalUserRequest<'a> {
+    pub fn user_id(&self) -> Option<i32> {
+        self.token.as_ref().map(|token| token.user_id)
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for OptionalUserRequest<'a> {
+    type Error = JsonResponse;
+
+    fn from_request(
+        request: &'a Request<'r>,
+    ) -> request::Outcome<OptionalUserRequest<'a>, Self::Error> {
+        if request.headers().get_one("Authorization").is_none() {
+            return match PublicRequest::from_request(&request) {
+                request::Outcome::Success(public) => Outcome::Success(OptionalUserRequest {
+                    state: public.state,
+                    token: None,
+                }),
+                request::Outcome::Failure(err) => request::Outcome::Failure(err),
+                request::Outcome::Forward(fwd) => request::Outcome::Forward(fwd),
+            };
+        }
+        match UserRequest::from_request(&request) {
+            request::Outcome::Success(user) => Outcome::Success(OptionalUserRequest {
+                state: user.state,
+                token: Some(user.token),
+            }),
+            request::Outcome::Failure(err) => request::Outcome::Failure(err),
+            request::Outcome::Forward(fwd) => request::Outcome::Forward(fwd),
+        }
+    }
+}
+
 pub struct AdminRequest<'a>(pub UserRequest<'a>);
 
 impl<'a> Deref for AdminRequest<'a> {
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use diesel::pg::PgConnection;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use crate::models::item::{ItemVisibility, NewShoppingItem, ShoppingItem, UnitType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogFormat {
+    Csv,
+    Json,
+}
+
+impl CatalogFormat {
+    pub fn from_name(name: &str) -> Option<CatalogFormat> {
+        match name {
+            "csv" => Some(CatalogFormat::Csv),
+            "json" => Some(CatalogFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A catalog item as it appears in an import file. Exported files carry an `id` column
+/// too, which is ignored on import.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CatalogRow {
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub default_unit_type: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RowError {
+    pub row: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateRow {
+    pub row: usize,
+    pub name: String,
+    pub existing_item_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub imported: usize,
+    pub errors: Vec<RowError>,
+    pub duplicates: Vec<DuplicateRow>,
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Parses every record in the body, keeping per-row failures so they can be reported
+/// alongside validation errors.
+pub fn parse_rows(
+    format: CatalogFormat,
+    body: &str,
+) -> Result<Vec<Result<CatalogRow, String>>, String> {
+    match format {
+        CatalogFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(body.as_bytes());
+            Ok(reader
+                .deserialize::<CatalogRow>()
+                .map(|row| row.map_err(|err| err.to_string()))
+                .collect())
+        }
+        CatalogFormat::Json => {
+            let values: Vec<serde_json::Value> =
+                serde_json::from_str(body).map_err(|err| err.to_string())?;
+            Ok(values
+                .into_iter()
+                .map(|value| serde_json::from_value(value).map_err(|err| err.to_string()))
+                .collect())
+        }
+    }
+}
+
+fn validate_row(row: &CatalogRow) -> Result<NewShoppingItem, String> {
+    let default_unit_type = UnitType::try_from(normalize_name(&row.default_unit_type))
+        .map_err(|_| format!("unknown unit type '{}'", row.default_unit_type))?;
+    let item = NewShoppingItem {
+        name: String::from(row.name.trim()),
+        description: row
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(String::from),
+        image_url: row
+            .image_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(String::from),
+        default_unit_type,
+        owner_id: None,
+    };
+    item.validate()?;
+    Ok(item)
+}
+
+/// Validates the parsed rows and, unless this is a dry run, inserts them. Rows whose
+/// name matches an existing item or an earlier row are skipped as duplicates. Nothing
+/// is inserted if any row is invalid.
+pub fn import_catalog(
+    conn: &PgConnection,
+    rows: Vec<Result<CatalogRow, String>>,
+    dry_run: bool,
+) -> Result<ImportReport, diesel::result::Error> {
+    let existing: HashMap<String, i32> = ShoppingItem::get_all_items(conn, ItemVisibility::Global)
+        .into_iter()
+        .map(|item| (normalize_name(&item.name), item.id))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for (index, parsed) in rows.into_iter().enumerate() {
+        let row = index + 1;
+        let item = match parsed.and_then(|catalog_row| validate_row(&catalog_row)) {
+            Ok(item) => item,
+            Err(error) => {
+                errors.push(RowError { row, error });
+                continue;
+            }
+        };
+        let key = normalize_name(&item.name);
+        if existing.contains_key(&key) || !seen.insert(key.clone()) {
+            duplicates.push(DuplicateRow {
+                row,
+                name: item.name,
+                existing_item_id: existing.get(&key).copied(),
+            });
+        } else {
+            items.push(item);
+        }
+    }
+
+    let imported = if dry_run || !errors.is_empty() {
+        0
+    } else {
+        ShoppingItem::insert_items(conn, &items)?
+    };
+    Ok(ImportReport {
+        dry_run,
+        imported,
+        errors,
+        duplicates,
+    })
+}
+
+#[derive(Serialize)]
+struct ExportRow<'a> {
+    id: i32,
+    name: &'a str,
+    description: Option<&'a str>,
+    image_url: Option<&'a str>,
+    default_unit_type: UnitType,
+}
+
+pub fn export_catalog(conn: &PgConnection, format: CatalogFormat) -> Result<String, String> {
+    let items = ShoppingItem::get_all_items(conn, ItemVisibility::Global);
+    match format {
+        CatalogFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for item in items.iter() {
+                writer
+                    .serialize(ExportRow {
+                        id: item.id,
+                        name: &item.name,
+                        description: item.description.as_deref(),
+                        image_url: item.image_url.as_deref(),
+                        default_unit_type: item.default_unit_type,
+                    })
+                    .map_err(|err| err.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|err| err.to_string())?;
+            String::from_utf8(bytes).map_err(|err| err.to_string())
+        }
+        CatalogFormat::Json => serde_json::to_string(&items).map_err(|err| err.to_string()),
+    }
+}
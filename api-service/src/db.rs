@@ -10,6 +10,7 @@ use rocket::{Request, State};
 use sha2::Sha256;
 
 use super::models::auth::{NewUser, User};
+use super::storage::BlobStore;
 
 pub type HmacSha256 = Hmac<Sha256>;
 
@@ -21,6 +22,7 @@ pub struct StateInstance<'a> {
     pub pwd_salt: String,
     pub pwd_config: &'a argon2::Config<'static>,
     pub jwt_key: HmacSha256,
+    pub blob_store: &'a dyn BlobStore,
 }
 
 impl<'a> StateInstance<'a> {
@@ -46,6 +48,7 @@ pub struct ApplicationState {
     pub pwd_salt: String,
     pub pwd_config: argon2::Config<'static>,
     pub jwt_key: HmacSha256,
+    pub blob_store: Box<dyn BlobStore + Send + Sync>,
 }
 
 impl ApplicationState {
@@ -57,6 +60,7 @@ impl ApplicationState {
                 pwd_salt: self.pwd_salt.clone(),
                 pwd_config: &self.pwd_config,
                 jwt_key: self.jwt_key.clone(),
+                blob_store: self.blob_store.as_ref(),
             }),
             Err(_) => Err(()),
         }
@@ -86,7 +90,7 @@ fn create_super_user(state: &StateInstance) {
     };
 }
 
-pub fn init_pool(db_url: String) -> ApplicationState {
+pub fn init_pool(db_url: String, blob_store: Box<dyn BlobStore + Send + Sync>) -> ApplicationState {
     let manager = ConnectionManager::<PgConnection>::new(db_url);
     let pool = r2d2::Pool::builder()
         .max_size(1)
@@ -97,6 +101,7 @@ pub fn init_pool(db_url: String) -> ApplicationState {
         pwd_salt: String::from("SaltSaltSaltSalt"),
         pwd_config: argon2::Config::default(),
         jwt_key: HmacSha256::new_from_slice(b"secret-key").unwrap(),
+        blob_store,
     };
     create_super_user(&state.get_instance().unwrap());
     state
@@ -0,0 +1,153 @@
+use std::io::{Cursor, Read};
+
+use image::{ImageFormat, ImageOutputFormat};
+use rocket::http::{ContentType, Status};
+
+use crate::storage::BlobStore;
+
+pub const MAX_IMAGE_SIZE: u64 = 5 * 1024 * 1024;
+
+const ALLOWED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSize {
+    Original,
+    Small,
+    Medium,
+}
+
+impl ImageSize {
+    pub const ALL: [ImageSize; 3] = [ImageSize::Original, ImageSize::Small, ImageSize::Medium];
+    pub const THUMBNAILS: [ImageSize; 2] = [ImageSize::Small, ImageSize::Medium];
+
+    pub fn from_name(name: &str) -> Option<ImageSize> {
+        match name {
+            "original" => Some(ImageSize::Original),
+            "small" => Some(ImageSize::Small),
+            "medium" => Some(ImageSize::Medium),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ImageSize::Original => "original",
+            ImageSize::Small => "small",
+            ImageSize::Medium => "medium",
+        }
+    }
+
+    fn max_dimension(&self) -> Option<u32> {
+        match self {
+            ImageSize::Original => None,
+            ImageSize::Small => Some(128),
+            ImageSize::Medium => Some(512),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageUploadError {
+    TooLarge,
+    UnsupportedFormat,
+    Storage,
+}
+
+impl ImageUploadError {
+    pub fn status(&self) -> Status {
+        match self {
+            ImageUploadError::TooLarge => Status::PayloadTooLarge,
+            ImageUploadError::UnsupportedFormat => Status::UnsupportedMediaType,
+            ImageUploadError::Storage => Status::InternalServerError,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            ImageUploadError::TooLarge => "Image is too large",
+            ImageUploadError::UnsupportedFormat => "Image must be a PNG, JPEG, GIF or WebP",
+            ImageUploadError::Storage => "Failed to store image",
+        }
+    }
+}
+
+fn item_image_key(item_id: i32, size: ImageSize) -> String {
+    format!("items/{}/{}", item_id, size.name())
+}
+
+pub fn item_image_url(item_id: i32) -> String {
+    format!("/api/v1/items/{}/image", item_id)
+}
+
+/// Reads at most `MAX_IMAGE_SIZE` bytes, failing if the stream holds more.
+pub fn read_limited<R: Read>(reader: R) -> Result<Vec<u8>, ImageUploadError> {
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_IMAGE_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|_| ImageUploadError::Storage)?;
+    if bytes.len() as u64 > MAX_IMAGE_SIZE {
+        Err(ImageUploadError::TooLarge)
+    } else {
+        Ok(bytes)
+    }
+}
+
+/// Validates an uploaded image, stores the original and writes a PNG thumbnail for each
+/// thumbnail size, replacing any image the item already had.
+pub fn store_item_image(
+    store: &dyn BlobStore,
+    item_id: i32,
+    bytes: &[u8],
+) -> Result<(), ImageUploadError> {
+    let format = image::guess_format(bytes).map_err(|_| ImageUploadError::UnsupportedFormat)?;
+    if !ALLOWED_FORMATS.contains(&format) {
+        return Err(ImageUploadError::UnsupportedFormat);
+    }
+    let decoded = image::load_from_memory_with_format(bytes, format)
+        .map_err(|_| ImageUploadError::UnsupportedFormat)?;
+
+    store
+        .put(&item_image_key(item_id, ImageSize::Original), bytes)
+        .map_err(|_| ImageUploadError::Storage)?;
+    for size in ImageSize::THUMBNAILS.iter() {
+        let max_dimension = size.max_dimension().unwrap_or(u32::MAX);
+        let thumbnail = decoded.thumbnail(max_dimension, max_dimension);
+        let mut encoded = Cursor::new(Vec::new());
+        thumbnail
+            .write_to(&mut encoded, ImageOutputFormat::Png)
+            .map_err(|_| ImageUploadError::Storage)?;
+        store
+            .put(&item_image_key(item_id, *size), encoded.get_ref())
+            .map_err(|_| ImageUploadError::Storage)?;
+    }
+    Ok(())
+}
+
+/// Loads a stored item image along with the content type to serve it as.
+pub fn load_item_image(
+    store: &dyn BlobStore,
+    item_id: i32,
+    size: ImageSize,
+) -> Option<(ContentType, Vec<u8>)> {
+    let bytes = store.get(&item_image_key(item_id, size)).ok()?;
+    let content_type = match image::guess_format(&bytes).ok()? {
+        ImageFormat::Png => ContentType::PNG,
+        ImageFormat::Jpeg => ContentType::JPEG,
+        ImageFormat::Gif => ContentType::GIF,
+        ImageFormat::WebP => ContentType::WEBP,
+        _ => ContentType::Binary,
+    };
+    Some((content_type, bytes))
+}
+
+pub fn delete_item_images(store: &dyn BlobStore, item_id: i32) -> bool {
+    ImageSize::ALL.iter().fold(true, |success, size| {
+        store.delete(&item_image_key(item_id, *size)).is_ok() && success
+    })
+}
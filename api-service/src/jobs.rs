@@ -0,0 +1,25 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::db::ConnectionPool;
+use crate::models::notification::Notification;
+
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const EXPIRY_NOTICE_DAYS: i32 = 3;
+
+/// Periodically records notifications for pantry stock that is about to expire.
+pub fn spawn_expiry_notifier(pool: ConnectionPool) {
+    thread::spawn(move || loop {
+        match pool.get() {
+            Ok(conn) => {
+                match Notification::record_expiry_notifications(&conn, EXPIRY_NOTICE_DAYS) {
+                    Ok(count) if count > 0 => println!("Recorded {} expiry notifications.", count),
+                    Ok(_) => {}
+                    Err(err) => println!("Failed to record expiry notifications: {}", err),
+                }
+            }
+            Err(err) => println!("Expiry notifier could not connect to database: {}", err),
+        }
+        thread::sleep(EXPIRY_CHECK_INTERVAL);
+    });
+}
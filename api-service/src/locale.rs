@@ -0,0 +1,70 @@
+use rocket::request::{self, FromRequest};
+use rocket::{Outcome, Request};
+
+const MAX_LOCALE_LENGTH: usize = 16;
+
+/// Lowercases a BCP 47 style language tag such as `en-GB`, returning `None` if it is not
+/// one.
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    let mut subtags = tag.split('-');
+    let language = subtags.next()?;
+    let valid = tag.len() <= MAX_LOCALE_LENGTH
+        && (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+    if valid {
+        Some(tag)
+    } else {
+        None
+    }
+}
+
+/// The locales a client asked for in its `Accept-Language` header, most preferred first.
+/// Each regional tag is followed by its base language, so `fr-CH` also accepts `fr`.
+pub struct AcceptLanguage(pub Vec<String>);
+
+impl AcceptLanguage {
+    pub fn parse(header: &str) -> AcceptLanguage {
+        let mut weighted: Vec<(f32, String)> = header
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';');
+                let locale = normalize_locale(params.next()?)?;
+                let weight = params
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                if weight > 0.0 {
+                    Some((weight, locale))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // Stable, so tags with equal weight keep the order the client sent them in.
+        weighted.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut locales: Vec<String> = Vec::new();
+        for (_, locale) in weighted {
+            let language = locale.split('-').next().map(String::from);
+            for candidate in std::iter::once(locale).chain(language) {
+                if !locales.contains(&candidate) {
+                    locales.push(candidate);
+                }
+            }
+        }
+        AcceptLanguage(locales)
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for AcceptLanguage {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<AcceptLanguage, Self::Error> {
+        let header = request.headers().get_one("Accept-Language").unwrap_or("");
+        Outcome::Success(AcceptLanguage::parse(header))
+    }
+}
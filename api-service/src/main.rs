@@ -21,11 +21,17 @@ use rocket::http::Method;
 use rocket_cors::{AllowedOrigins, CorsOptions};
 
 mod auth;
+mod catalog;
 mod db;
+mod images;
+mod jobs;
+mod locale;
 mod models;
 mod responses;
 mod routes;
 mod schema;
+mod storage;
+mod text_import;
 mod utils;
 
 fn rocket() -> rocket::Rocket {
@@ -33,7 +39,15 @@ fn rocket() -> rocket::Rocket {
 
     let database_url = env::var("DATABASE_URL").expect("set DATABASE_URL");
 
-    let pool = db::init_pool(database_url);
+    let image_storage_path =
+        env::var("IMAGE_STORAGE_PATH").unwrap_or(String::from("storage/images"));
+
+    let pool = db::init_pool(
+        database_url,
+        Box::new(storage::LocalBlobStore::new(image_storage_path)),
+    );
+
+    jobs::spawn_expiry_notifier(pool.connection_pool.clone());
 
     let cors = CorsOptions::default()
         .allowed_origins(AllowedOrigins::all())
@@ -59,7 +73,74 @@ fn rocket() -> rocket::Rocket {
                 routes::users::delete_user,
                 routes::item::get_all_items,
                 routes::item::post_new_item,
+                routes::item::post_parse_text,
                 routes::item::delete_item,
+                routes::item::post_private_item,
+                routes::item::delete_private_item,
+                routes::item::get_item_image,
+                routes::item::post_item_image,
+                routes::catalog::get_catalog_export,
+                routes::catalog::post_catalog_import,
+                routes::alias::get_item_aliases,
+                routes::alias::post_item_alias,
+                routes::alias::delete_item_alias,
+                routes::translation::get_item_translations,
+                routes::translation::post_item_translation,
+                routes::translation::delete_item_translation,
+                routes::duplicate::get_duplicate_items,
+                routes::duplicate::post_merge_items,
+                routes::proposal::get_my_proposals,
+                routes::proposal::get_pending_proposals,
+                routes::proposal::post_new_proposal,
+                routes::proposal::post_approve_proposal,
+                routes::proposal::post_reject_proposal,
+                routes::proposal::post_merge_proposal,
+                routes::barcode::get_item_by_barcode,
+                routes::barcode::get_item_barcodes,
+                routes::barcode::post_item_barcode,
+                routes::barcode::delete_item_barcode,
+                routes::meal_plan::get_meal_plan,
+                routes::meal_plan::get_meal_plan_ingredients,
+                routes::meal_plan::post_meal_plan_entry,
+                routes::meal_plan::delete_meal_plan_entry,
+                routes::notification::get_notifications,
+                routes::notification::patch_notification_read,
+                routes::nutrition::get_item_nutrition,
+                routes::nutrition::post_item_nutrition,
+                routes::nutrition::get_recipe_nutrition,
+                routes::pantry::get_pantry,
+                routes::pantry::get_expiring,
+                routes::pantry::get_low_stock,
+                routes::pantry::post_pantry_item,
+                routes::pantry::post_consume,
+                routes::pantry::delete_pantry_item,
+                routes::pantry::post_threshold,
+                routes::pantry::delete_threshold,
+                routes::price::get_latest_prices,
+                routes::price::get_cheapest_price,
+                routes::price::get_price_history,
+                routes::price::post_new_price,
+                routes::recipe::get_all_recipes,
+                routes::recipe::get_recipe,
+                routes::recipe::get_recipe_ingredients,
+                routes::recipe::post_new_recipe,
+                routes::recipe::patch_recipe,
+                routes::recipe::delete_recipe,
+                routes::restriction::get_my_restrictions,
+                routes::restriction::post_my_restrictions,
+                routes::store::get_all_stores,
+                routes::store::get_nearby_stores,
+                routes::store::post_new_store,
+                routes::store::delete_store,
+                routes::template::get_templates,
+                routes::template::get_template,
+                routes::template::post_new_template,
+                routes::template::patch_template,
+                routes::template::delete_template,
+                routes::unit::get_units,
+                routes::variant::get_item_variants,
+                routes::variant::post_item_variant,
+                routes::variant::delete_item_variant,
             ],
         )
 }
@@ -0,0 +1,67 @@
+use crate::schema::item_alias;
+use crate::schema::item_alias::dsl::item_alias as all_aliases;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::VarChar;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+sql_function!(fn lower(x: VarChar) -> VarChar);
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct ItemAlias {
+    pub id: i32,
+    pub item_id: i32,
+    pub alias: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "item_alias"]
+pub struct NewItemAlias {
+    pub item_id: i32,
+    pub alias: String,
+}
+
+impl ItemAlias {
+    pub fn get_item_aliases(conn: &PgConnection, item_id: i32) -> Vec<ItemAlias> {
+        all_aliases
+            .filter(item_alias::item_id.eq(item_id))
+            .order(item_alias::alias.asc())
+            .load::<ItemAlias>(conn)
+            .expect("Error loading aliases")
+    }
+
+    pub fn get_alias_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<ItemAlias, diesel::result::Error> {
+        all_aliases.find(id).first::<ItemAlias>(conn)
+    }
+
+    /// Looks up an alias ignoring case, since aliases are unique regardless of case.
+    pub fn get_alias_by_name(
+        conn: &PgConnection,
+        alias: &str,
+    ) -> Result<ItemAlias, diesel::result::Error> {
+        all_aliases
+            .filter(lower(item_alias::alias).eq(alias.trim().to_lowercase()))
+            .first::<ItemAlias>(conn)
+    }
+
+    pub fn insert_alias(
+        conn: &PgConnection,
+        alias: &NewItemAlias,
+    ) -> Result<ItemAlias, diesel::result::Error> {
+        diesel::insert_into(item_alias::table)
+            .values(alias)
+            .get_result::<ItemAlias>(conn)
+    }
+
+    pub fn delete_alias(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(item_alias::table)
+            .filter(item_alias::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+}
@@ -0,0 +1,122 @@
+use crate::schema::item_barcode;
+use crate::schema::item_barcode::dsl::item_barcode as all_barcodes;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::item::{ItemVisibility, ShoppingItem};
+use super::variant::ItemVariant;
+
+const GTIN_LENGTHS: [usize; 4] = [8, 12, 13, 14];
+const GTIN_14_LENGTH: usize = 14;
+
+#[derive(Debug, Serialize, Deserialize, Queryable, Insertable)]
+#[table_name = "item_barcode"]
+pub struct ItemBarcode {
+    pub gtin: String,
+    pub item_id: i32,
+    /// The specific product the code is printed on, when known.
+    pub variant_id: Option<i32>,
+}
+
+/// Validates an EAN-8, UPC-A, EAN-13 or GTIN-14 code and returns it zero-padded to
+/// 14 digits so the same product always maps to the same key.
+pub fn normalize_gtin(code: &str) -> Option<String> {
+    let code = code.trim();
+    if !GTIN_LENGTHS.contains(&code.len()) || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let digits: Vec<u32> = code.chars().filter_map(|c| c.to_digit(10)).collect();
+    let (check_digit, payload) = digits.split_last()?;
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| if i % 2 == 0 { digit * 3 } else { *digit })
+        .sum();
+    if (10 - sum % 10) % 10 == *check_digit {
+        Some(format!("{:0>width$}", code, width = GTIN_14_LENGTH))
+    } else {
+        None
+    }
+}
+
+impl ItemBarcode {
+    /// The item a code belongs to, along with the variant it identifies if any.
+    pub fn get_item_by_gtin(
+        conn: &PgConnection,
+        gtin: &str,
+        visibility: ItemVisibility,
+    ) -> Result<(ShoppingItem, Option<ItemVariant>), diesel::result::Error> {
+        let barcode = all_barcodes.find(gtin).first::<ItemBarcode>(conn)?;
+        let item = ShoppingItem::get_item_by_id(conn, barcode.item_id, visibility)?;
+        let variant = match barcode.variant_id {
+            Some(variant_id) => Some(ItemVariant::get_variant_by_id(conn, variant_id)?),
+            None => None,
+        };
+        Ok((item, variant))
+    }
+
+    pub fn get_barcode(
+        conn: &PgConnection,
+        gtin: &str,
+    ) -> Result<ItemBarcode, diesel::result::Error> {
+        all_barcodes.find(gtin).first::<ItemBarcode>(conn)
+    }
+
+    pub fn get_item_barcodes(conn: &PgConnection, item_id: i32) -> Vec<ItemBarcode> {
+        all_barcodes
+            .filter(item_barcode::item_id.eq(item_id))
+            .order(item_barcode::gtin.asc())
+            .load::<ItemBarcode>(conn)
+            .expect("Error loading barcodes")
+    }
+
+    pub fn insert_barcode(conn: &PgConnection, barcode: &ItemBarcode) -> bool {
+        diesel::insert_into(item_barcode::table)
+            .values(barcode)
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn delete_barcode(conn: &PgConnection, gtin: &str) -> bool {
+        diesel::delete(item_barcode::table)
+            .filter(item_barcode::gtin.eq(gtin))
+            .execute(conn)
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_codes_padded_to_14_digits() {
+        assert_eq!(normalize_gtin("96385074").unwrap(), "00000096385074");
+        assert_eq!(normalize_gtin("036000291452").unwrap(), "00036000291452");
+        assert_eq!(normalize_gtin("4006381333931").unwrap(), "04006381333931");
+        assert_eq!(normalize_gtin("00012345600012").unwrap(), "00012345600012");
+    }
+
+    #[test]
+    fn rejects_wrong_check_digits() {
+        assert_eq!(normalize_gtin("96385075"), None);
+        assert_eq!(normalize_gtin("036000291453"), None);
+        assert_eq!(normalize_gtin("4006381333932"), None);
+        assert_eq!(normalize_gtin("00012345600013"), None);
+    }
+
+    #[test]
+    fn rejects_non_digits_and_other_lengths() {
+        assert_eq!(normalize_gtin("40063813339X1"), None);
+        assert_eq!(normalize_gtin("1234567"), None);
+    }
+
+    #[test]
+    fn ignores_surrounding_whitespace() {
+        assert_eq!(normalize_gtin(" 96385074\n").unwrap(), "00000096385074");
+    }
+}
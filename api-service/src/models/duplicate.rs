@@ -0,0 +1,146 @@
+use crate::schema::{
+    item_alias, item_barcode, item_price, item_proposal, item_variant, list_template_item,
+    pantry_item, recipe_ingredient, shopping_item,
+};
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Double, Int4, Int8, VarChar};
+use serde_derive::Serialize;
+
+/// A pair of items whose names are similar enough that one is probably a duplicate of
+/// the other.
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct DuplicateCandidate {
+    #[sql_type = "Int4"]
+    pub item_id: i32,
+    #[sql_type = "VarChar"]
+    pub item_name: String,
+    #[sql_type = "Int4"]
+    pub duplicate_id: i32,
+    #[sql_type = "VarChar"]
+    pub duplicate_name: String,
+    #[sql_type = "Double"]
+    pub similarity: f64,
+}
+
+/// Pairs of shared catalog items whose lowercased names have a trigram similarity of at
+/// least `threshold`, most similar first. The threshold is applied with the `%` operator
+/// so the trigram index on item names can be used; this sets pg_trgm's similarity
+/// limit for the connection.
+pub fn find_duplicates(conn: &PgConnection, threshold: f64, limit: i64) -> Vec<DuplicateCandidate> {
+    diesel::sql_query("SELECT set_limit($1::real)")
+        .bind::<Double, _>(threshold)
+        .execute(conn)
+        .expect("Error setting similarity limit");
+    diesel::sql_query(
+        "SELECT a.id AS item_id, a.name AS item_name, \
+                b.id AS duplicate_id, b.name AS duplicate_name, \
+                similarity(LOWER(a.name), LOWER(b.name))::float8 AS similarity \
+         FROM shopping_item a JOIN shopping_item b \
+           ON a.id < b.id AND LOWER(a.name) % LOWER(b.name) \
+         WHERE a.owner_id IS NULL AND b.owner_id IS NULL \
+         ORDER BY similarity DESC, a.id, b.id \
+         LIMIT $1",
+    )
+    .bind::<Int8, _>(limit)
+    .load::<DuplicateCandidate>(conn)
+    .expect("Error loading duplicate items")
+}
+
+/// Moves every reference to `duplicate_ids` onto `item_id` and deletes the duplicates,
+/// all in one transaction. The duplicates' names are kept as aliases of the surviving
+/// item. Where both items have a pantry threshold or translation for the same user or
+/// locale, or both have nutrition facts, the surviving item's one wins.
+pub fn merge_items(
+    conn: &PgConnection,
+    item_id: i32,
+    duplicate_ids: &[i32],
+) -> Result<(), diesel::result::Error> {
+    conn.transaction(|| {
+        diesel::update(item_price::table)
+            .filter(item_price::item_id.eq_any(duplicate_ids))
+            .set(item_price::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(item_barcode::table)
+            .filter(item_barcode::item_id.eq_any(duplicate_ids))
+            .set(item_barcode::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(recipe_ingredient::table)
+            .filter(recipe_ingredient::item_id.eq_any(duplicate_ids))
+            .set(recipe_ingredient::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(list_template_item::table)
+            .filter(list_template_item::item_id.eq_any(duplicate_ids))
+            .set(list_template_item::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(pantry_item::table)
+            .filter(pantry_item::item_id.eq_any(duplicate_ids))
+            .set(pantry_item::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(item_alias::table)
+            .filter(item_alias::item_id.eq_any(duplicate_ids))
+            .set(item_alias::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(item_proposal::table)
+            .filter(item_proposal::item_id.eq_any(duplicate_ids))
+            .set(item_proposal::item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(item_proposal::table)
+            .filter(item_proposal::resolved_item_id.eq_any(duplicate_ids))
+            .set(item_proposal::resolved_item_id.eq(item_id))
+            .execute(conn)?;
+        diesel::update(item_variant::table)
+            .filter(item_variant::item_id.eq_any(duplicate_ids))
+            .set(item_variant::item_id.eq(item_id))
+            .execute(conn)?;
+
+        // Rows keyed by item can only move if the surviving item has no row for the same
+        // key yet; the rest are removed along with the duplicates.
+        diesel::sql_query(
+            "UPDATE pantry_threshold SET item_id = $1 \
+             WHERE (user_id, item_id) IN ( \
+               SELECT DISTINCT ON (user_id) user_id, item_id FROM pantry_threshold \
+               WHERE item_id = ANY($2) \
+                 AND user_id NOT IN (SELECT user_id FROM pantry_threshold WHERE item_id = $1) \
+               ORDER BY user_id, item_id)",
+        )
+        .bind::<Int4, _>(item_id)
+        .bind::<Array<Int4>, _>(duplicate_ids)
+        .execute(conn)?;
+        diesel::sql_query(
+            "UPDATE item_translation SET item_id = $1 \
+             WHERE (item_id, locale) IN ( \
+               SELECT DISTINCT ON (locale) item_id, locale FROM item_translation \
+               WHERE item_id = ANY($2) \
+                 AND locale NOT IN (SELECT locale FROM item_translation WHERE item_id = $1) \
+               ORDER BY locale, item_id)",
+        )
+        .bind::<Int4, _>(item_id)
+        .bind::<Array<Int4>, _>(duplicate_ids)
+        .execute(conn)?;
+        diesel::sql_query(
+            "UPDATE item_nutrition SET item_id = $1 \
+             WHERE item_id = (SELECT MIN(item_id) FROM item_nutrition WHERE item_id = ANY($2)) \
+               AND NOT EXISTS (SELECT 1 FROM item_nutrition WHERE item_id = $1)",
+        )
+        .bind::<Int4, _>(item_id)
+        .bind::<Array<Int4>, _>(duplicate_ids)
+        .execute(conn)?;
+        diesel::sql_query(
+            "INSERT INTO item_alias (item_id, alias) \
+             SELECT $1, name FROM shopping_item \
+             WHERE id = ANY($2) \
+               AND LOWER(name) <> (SELECT LOWER(name) FROM shopping_item WHERE id = $1) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind::<Int4, _>(item_id)
+        .bind::<Array<Int4>, _>(duplicate_ids)
+        .execute(conn)?;
+
+        diesel::delete(shopping_item::table)
+            .filter(shopping_item::id.eq_any(duplicate_ids))
+            .execute(conn)?;
+        Ok(())
+    })
+}
@@ -1,7 +1,7 @@
-use crate::schema::shopping_item;
 use crate::schema::shopping_item::dsl::shopping_item as all_items;
+use crate::schema::{item_alias, item_nutrition, item_translation, shopping_item};
 use diesel;
-use diesel::pg::PgConnection;
+use diesel::pg::{Pg, PgConnection};
 use diesel::prelude::*;
 use serde::Deserialize;
 use serde_derive::Serialize;
@@ -9,6 +9,13 @@ use serde_derive::Serialize;
 use diesel::sql_types::VarChar;
 use diesel_enum::DbEnum;
 
+use super::nutrition::DietaryFilter;
+use super::unit::{Quantity, QuantityError};
+
+pub(crate) const MAX_NAME_LENGTH: usize = 128;
+pub(crate) const MAX_DESCRIPTION_LENGTH: usize = 512;
+pub(crate) const MAX_IMAGE_URL_LENGTH: usize = 256;
+
 #[derive(Debug)]
 pub struct UnitTypeError {
     pub msg: String,
@@ -40,6 +47,7 @@ pub struct ShoppingItem {
     pub description: Option<String>,
     pub image_url: Option<String>,
     pub default_unit_type: UnitType,
+    pub owner_id: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -49,20 +57,175 @@ pub struct NewShoppingItem {
     pub description: Option<String>,
     pub image_url: Option<String>,
     pub default_unit_type: UnitType,
+    pub owner_id: Option<i32>,
+}
+
+/// Which items a caller may see. Items with an owner are private to that user; the
+/// rest make up the shared catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemVisibility {
+    /// Every item, for admin tooling.
+    All,
+    /// Only the shared catalog.
+    Global,
+    /// The shared catalog plus the user's own private items.
+    User(i32),
+}
+
+impl ItemVisibility {
+    pub fn for_user(user_id: Option<i32>) -> ItemVisibility {
+        match user_id {
+            Some(user_id) => ItemVisibility::User(user_id),
+            None => ItemVisibility::Global,
+        }
+    }
+
+    fn visible_items(&self) -> shopping_item::BoxedQuery<'static, Pg> {
+        let query = all_items.into_boxed();
+        match *self {
+            ItemVisibility::All => query,
+            ItemVisibility::Global => query.filter(shopping_item::owner_id.is_null()),
+            ItemVisibility::User(user_id) => query.filter(
+                shopping_item::owner_id
+                    .is_null()
+                    .or(shopping_item::owner_id.eq(user_id)),
+            ),
+        }
+    }
+}
+
+impl NewShoppingItem {
+    /// Checks the fields fit the columns they are stored in.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.len() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "name must be between 1 and {} characters",
+                MAX_NAME_LENGTH
+            ));
+        }
+        if self
+            .description
+            .as_ref()
+            .map_or(false, |d| d.len() > MAX_DESCRIPTION_LENGTH)
+        {
+            return Err(format!(
+                "description must be at most {} characters",
+                MAX_DESCRIPTION_LENGTH
+            ));
+        }
+        if self
+            .image_url
+            .as_ref()
+            .map_or(false, |url| url.len() > MAX_IMAGE_URL_LENGTH)
+        {
+            return Err(format!(
+                "image_url must be at most {} characters",
+                MAX_IMAGE_URL_LENGTH
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Why a quantity given for an item was refused.
+#[derive(Debug)]
+pub enum ItemQuantityError {
+    NotPositive,
+    ItemNotFound,
+    WrongUnit(QuantityError),
 }
 
 impl ShoppingItem {
-    pub fn get_all_items(conn: &PgConnection) -> Vec<ShoppingItem> {
-        all_items
+    pub fn check_quantity(&self, quantity: &Quantity) -> Result<(), QuantityError> {
+        quantity.check_unit_type(self.default_unit_type)
+    }
+
+    /// Checks the item exists and `quantity` is a positive amount in a unit it can be
+    /// measured in.
+    pub fn validate_quantity(
+        conn: &PgConnection,
+        item_id: i32,
+        quantity: &Quantity,
+        visibility: ItemVisibility,
+    ) -> Result<(), ItemQuantityError> {
+        if quantity.amount <= 0.0 {
+            return Err(ItemQuantityError::NotPositive);
+        }
+        let item = ShoppingItem::get_item_by_id(conn, item_id, visibility)
+            .map_err(|_| ItemQuantityError::ItemNotFound)?;
+        item.check_quantity(quantity)
+            .map_err(ItemQuantityError::WrongUnit)
+    }
+
+    pub fn get_all_items(conn: &PgConnection, visibility: ItemVisibility) -> Vec<ShoppingItem> {
+        visibility
+            .visible_items()
             .order(shopping_item::id.desc())
             .load::<ShoppingItem>(conn)
             .expect("Error loading items")
     }
 
-    pub fn search_all_items(conn: &PgConnection, query: &str) -> Vec<ShoppingItem> {
-        all_items
-            .order(shopping_item::id.desc())
-            .filter(shopping_item::name.ilike(["%", &query, "%"].join("")))
+    /// Finds items whose name, an alias or a localized name contains the query.
+    pub fn search_all_items(
+        conn: &PgConnection,
+        query: &str,
+        visibility: ItemVisibility,
+    ) -> Vec<ShoppingItem> {
+        ShoppingItem::find_items(conn, Some(query), DietaryFilter::default(), visibility)
+    }
+
+    /// Lists items, optionally only those matching a search query (see
+    /// `search_all_items`) and known to suit the given diet.
+    pub fn find_items(
+        conn: &PgConnection,
+        query: Option<&str>,
+        dietary: DietaryFilter,
+        visibility: ItemVisibility,
+    ) -> Vec<ShoppingItem> {
+        let mut items = visibility.visible_items().order(shopping_item::id.desc());
+        if let Some(query) = query {
+            let pattern = ["%", &query, "%"].join("");
+            let alias_matches = item_alias::table
+                .select(item_alias::item_id)
+                .filter(item_alias::alias.ilike(pattern.clone()));
+            let translation_matches = item_translation::table
+                .select(item_translation::item_id)
+                .filter(item_translation::name.ilike(pattern.clone()));
+            items = items.filter(
+                shopping_item::name
+                    .ilike(pattern)
+                    .or(shopping_item::id.eq_any(alias_matches))
+                    .or(shopping_item::id.eq_any(translation_matches)),
+            );
+        }
+        if dietary.vegan {
+            items = items.filter(
+                shopping_item::id.eq_any(
+                    item_nutrition::table
+                        .select(item_nutrition::item_id)
+                        .filter(item_nutrition::vegan.eq(true)),
+                ),
+            );
+        }
+        if dietary.gluten_free {
+            items = items.filter(
+                shopping_item::id.eq_any(
+                    item_nutrition::table
+                        .select(item_nutrition::item_id)
+                        .filter(item_nutrition::gluten_free.eq(true)),
+                ),
+            );
+        }
+        if dietary.nut_free {
+            items = items.filter(
+                shopping_item::id.eq_any(
+                    item_nutrition::table
+                        .select(item_nutrition::item_id)
+                        .filter(item_nutrition::contains_nuts.eq(false)),
+                ),
+            );
+        }
+        items
             .load::<ShoppingItem>(conn)
             .expect("Error loading items")
     }
@@ -70,13 +233,52 @@ impl ShoppingItem {
     pub fn get_item_by_id(
         conn: &PgConnection,
         id: i32,
+        visibility: ItemVisibility,
     ) -> Result<ShoppingItem, diesel::result::Error> {
-        all_items.find(id).first::<ShoppingItem>(conn)
+        visibility
+            .visible_items()
+            .filter(shopping_item::id.eq(id))
+            .first::<ShoppingItem>(conn)
+    }
+
+    pub fn get_items_by_ids(
+        conn: &PgConnection,
+        ids: &[i32],
+        visibility: ItemVisibility,
+    ) -> Vec<ShoppingItem> {
+        visibility
+            .visible_items()
+            .filter(shopping_item::id.eq_any(ids))
+            .order(shopping_item::name.asc())
+            .load::<ShoppingItem>(conn)
+            .expect("Error loading items")
     }
 
-    pub fn insert_item(conn: &PgConnection, item: &NewShoppingItem) -> bool {
+    pub fn insert_item(
+        conn: &PgConnection,
+        item: &NewShoppingItem,
+    ) -> Result<ShoppingItem, diesel::result::Error> {
         diesel::insert_into(shopping_item::table)
             .values(item)
+            .get_result::<ShoppingItem>(conn)
+    }
+
+    /// Inserts all items in a single transaction, returning how many were inserted.
+    pub fn insert_items(
+        conn: &PgConnection,
+        items: &[NewShoppingItem],
+    ) -> Result<usize, diesel::result::Error> {
+        conn.transaction(|| {
+            diesel::insert_into(shopping_item::table)
+                .values(items)
+                .execute(conn)
+        })
+    }
+
+    pub fn set_image_url(conn: &PgConnection, id: i32, image_url: Option<&str>) -> bool {
+        diesel::update(shopping_item::table)
+            .filter(shopping_item::id.eq(id))
+            .set(shopping_item::image_url.eq(image_url))
             .execute(conn)
             .is_ok()
     }
@@ -87,12 +289,4 @@ impl ShoppingItem {
             .execute(conn)
             .is_ok()
     }
-
-    pub fn get_last_inserted_item(
-        conn: &PgConnection,
-    ) -> Result<ShoppingItem, diesel::result::Error> {
-        all_items
-            .order(shopping_item::id.desc())
-            .first::<ShoppingItem>(conn)
-    }
 }
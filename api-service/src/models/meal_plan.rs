@@ -0,0 +1,128 @@
+use crate::schema::meal_plan_entry;
+use crate::schema::meal_plan_entry::dsl::meal_plan_entry as all_entries;
+use chrono::NaiveDate;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use diesel::sql_types::VarChar;
+use diesel_enum::DbEnum;
+
+use super::pantry::PantryItem;
+use super::recipe::Recipe;
+use super::unit::{aggregate_by_item, Quantity};
+
+#[derive(Debug)]
+pub struct MealSlotError {
+    pub msg: String,
+    pub status: u16,
+}
+
+impl MealSlotError {
+    fn not_found(msg: String) -> Self {
+        Self { msg, status: 404 }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, DbEnum,
+)]
+#[sql_type = "VarChar"]
+#[error_fn = "MealSlotError::not_found"]
+#[error_type = "MealSlotError"]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct MealPlanEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub planned_for: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: i32,
+    pub servings: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "meal_plan_entry"]
+pub struct NewMealPlanEntry {
+    pub user_id: i32,
+    pub planned_for: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: i32,
+    pub servings: i32,
+}
+
+impl MealPlanEntry {
+    pub fn get_entries_between(
+        conn: &PgConnection,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Vec<MealPlanEntry> {
+        all_entries
+            .filter(meal_plan_entry::user_id.eq(user_id))
+            .filter(meal_plan_entry::planned_for.between(from, to))
+            .order((
+                meal_plan_entry::planned_for.asc(),
+                meal_plan_entry::id.asc(),
+            ))
+            .load::<MealPlanEntry>(conn)
+            .expect("Error loading meal plan")
+    }
+
+    /// The ingredients still to buy for the planned meals in the date range: scaled to
+    /// each meal's servings, merged per item and less what is already in the pantry.
+    pub fn get_ingredients_between(
+        conn: &PgConnection,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<(i32, Quantity)>, diesel::result::Error> {
+        let mut ingredients = Vec::new();
+        for entry in MealPlanEntry::get_entries_between(conn, user_id, from, to) {
+            let details = Recipe::get_recipe_details(conn, entry.recipe_id)?;
+            ingredients.extend(details.scaled_ingredients(entry.servings));
+        }
+        Ok(PantryItem::subtract_stock(
+            conn,
+            user_id,
+            aggregate_by_item(ingredients),
+        ))
+    }
+
+    pub fn get_entry_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<MealPlanEntry, diesel::result::Error> {
+        all_entries.find(id).first::<MealPlanEntry>(conn)
+    }
+
+    pub fn insert_entry(conn: &PgConnection, entry: &NewMealPlanEntry) -> bool {
+        diesel::insert_into(meal_plan_entry::table)
+            .values(entry)
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn delete_entry(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(meal_plan_entry::table)
+            .filter(meal_plan_entry::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn get_last_inserted_entry(
+        conn: &PgConnection,
+    ) -> Result<MealPlanEntry, diesel::result::Error> {
+        all_entries
+            .order(meal_plan_entry::id.desc())
+            .first::<MealPlanEntry>(conn)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::schema::notification;
+use crate::schema::notification::dsl::notification as all_notifications;
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{Int4, VarChar};
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use diesel_enum::DbEnum;
+
+#[derive(Debug)]
+pub struct NotificationKindError {
+    pub msg: String,
+    pub status: u16,
+}
+
+impl NotificationKindError {
+    fn not_found(msg: String) -> Self {
+        Self { msg, status: 404 }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, DbEnum,
+)]
+#[sql_type = "VarChar"]
+#[error_fn = "NotificationKindError::not_found"]
+#[error_type = "NotificationKindError"]
+pub enum NotificationKind {
+    ExpiringSoon,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct Notification {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub pantry_item_id: Option<i32>,
+    pub is_read: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl Notification {
+    pub fn get_notifications(conn: &PgConnection, user_id: i32) -> Vec<Notification> {
+        all_notifications
+            .filter(notification::user_id.eq(user_id))
+            .order(notification::created_at.desc())
+            .load::<Notification>(conn)
+            .expect("Error loading notifications")
+    }
+
+    pub fn get_notification_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<Notification, diesel::result::Error> {
+        all_notifications.find(id).first::<Notification>(conn)
+    }
+
+    pub fn mark_read(conn: &PgConnection, id: i32) -> bool {
+        diesel::update(notification::table)
+            .filter(notification::id.eq(id))
+            .set(notification::is_read.eq(true))
+            .execute(conn)
+            .is_ok()
+    }
+
+    /// Records an expiry notification for every pantry batch that expires within
+    /// `within_days` days and has not been notified about yet. Returns how many were added.
+    pub fn record_expiry_notifications(
+        conn: &PgConnection,
+        within_days: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::sql_query(
+            "INSERT INTO notification (user_id, kind, message, pantry_item_id) \
+             SELECT p.user_id, $1, \
+                    i.name || ' expires on ' || to_char(p.expiry_date, 'YYYY-MM-DD'), p.id \
+             FROM pantry_item p JOIN shopping_item i ON i.id = p.item_id \
+             WHERE p.expiry_date <= CURRENT_DATE + $2 \
+             ON CONFLICT (pantry_item_id, kind) DO NOTHING",
+        )
+        .bind::<VarChar, _>(NotificationKind::ExpiringSoon)
+        .bind::<Int4, _>(within_days)
+        .execute(conn)
+    }
+}
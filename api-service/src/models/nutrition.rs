@@ -0,0 +1,159 @@
+use crate::schema::item_nutrition;
+use crate::schema::item_nutrition::dsl::item_nutrition as all_nutrition;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::item::UnitType;
+use super::unit::{Quantity, Unit};
+
+/// Nutrition facts per 100 g (or 100 ml for liquids) and dietary flags for an item.
+/// Missing values are unknown. Items sold by count can give the weight of one piece so
+/// their nutrition can still be worked out.
+#[derive(Debug, Serialize, Deserialize, Queryable, Insertable, AsChangeset)]
+#[table_name = "item_nutrition"]
+#[changeset_options(treat_none_as_null = "true")]
+pub struct ItemNutrition {
+    pub item_id: i32,
+    pub energy_kcal: Option<f64>,
+    pub protein_g: Option<f64>,
+    pub fat_g: Option<f64>,
+    pub carbohydrate_g: Option<f64>,
+    pub sugar_g: Option<f64>,
+    pub salt_g: Option<f64>,
+    pub fibre_g: Option<f64>,
+    pub unit_weight_g: Option<f64>,
+    pub vegan: Option<bool>,
+    pub gluten_free: Option<bool>,
+    pub contains_nuts: Option<bool>,
+}
+
+/// Restricts item searches to items known to suit a diet. Unset filters match any item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DietaryFilter {
+    pub vegan: bool,
+    pub gluten_free: bool,
+    pub nut_free: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct NutritionTotals {
+    pub energy_kcal: f64,
+    pub protein_g: f64,
+    pub fat_g: f64,
+    pub carbohydrate_g: f64,
+    pub sugar_g: f64,
+    pub salt_g: f64,
+    pub fibre_g: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NutritionSummary {
+    #[serde(flatten)]
+    pub totals: NutritionTotals,
+    /// Items left out of the totals because they have no nutrition facts or their
+    /// quantity could not be converted to grams or millilitres.
+    pub missing_item_ids: Vec<i32>,
+    /// Items counted in the totals that lack some nutrient values, so the totals
+    /// understate those nutrients.
+    pub incomplete_item_ids: Vec<i32>,
+}
+
+impl ItemNutrition {
+    pub fn get_item_nutrition(
+        conn: &PgConnection,
+        item_id: i32,
+    ) -> Result<ItemNutrition, diesel::result::Error> {
+        all_nutrition.find(item_id).first::<ItemNutrition>(conn)
+    }
+
+    pub fn get_items_nutrition(conn: &PgConnection, item_ids: &[i32]) -> Vec<ItemNutrition> {
+        all_nutrition
+            .filter(item_nutrition::item_id.eq_any(item_ids))
+            .load::<ItemNutrition>(conn)
+            .expect("Error loading nutrition")
+    }
+
+    /// Replaces all of an item's nutrition facts and flags.
+    pub fn upsert_nutrition(
+        conn: &PgConnection,
+        nutrition: &ItemNutrition,
+    ) -> Result<ItemNutrition, diesel::result::Error> {
+        diesel::insert_into(item_nutrition::table)
+            .values(nutrition)
+            .on_conflict(item_nutrition::item_id)
+            .do_update()
+            .set(nutrition)
+            .get_result::<ItemNutrition>(conn)
+    }
+
+    /// Whether every nutrient that goes into the totals is known.
+    fn is_complete(&self) -> bool {
+        [
+            self.energy_kcal,
+            self.protein_g,
+            self.fat_g,
+            self.carbohydrate_g,
+            self.sugar_g,
+            self.salt_g,
+            self.fibre_g,
+        ]
+        .iter()
+        .all(Option::is_some)
+    }
+
+    /// How many 100 g or 100 ml portions `quantity` of the item amounts to.
+    fn portions(&self, quantity: &Quantity) -> Option<f64> {
+        let unit_type = quantity.unit.unit_type();
+        let base = quantity.convert_to(Unit::base_unit(unit_type)).ok()?;
+        match unit_type {
+            UnitType::Mass | UnitType::Capacity => Some(base.amount / 100.0),
+            UnitType::Count => Some(base.amount * self.unit_weight_g? / 100.0),
+        }
+    }
+}
+
+impl NutritionTotals {
+    fn add(&mut self, nutrition: &ItemNutrition, portions: f64) {
+        let scaled = |value: Option<f64>| value.unwrap_or(0.0) * portions;
+        self.energy_kcal += scaled(nutrition.energy_kcal);
+        self.protein_g += scaled(nutrition.protein_g);
+        self.fat_g += scaled(nutrition.fat_g);
+        self.carbohydrate_g += scaled(nutrition.carbohydrate_g);
+        self.sugar_g += scaled(nutrition.sugar_g);
+        self.salt_g += scaled(nutrition.salt_g);
+        self.fibre_g += scaled(nutrition.fibre_g);
+    }
+}
+
+/// Adds up the nutrition of the given item quantities.
+pub fn sum_nutrition(conn: &PgConnection, entries: &[(i32, Quantity)]) -> NutritionSummary {
+    let item_ids: Vec<i32> = entries.iter().map(|(item_id, _)| *item_id).collect();
+    let nutrition = ItemNutrition::get_items_nutrition(conn, &item_ids);
+    let mut totals = NutritionTotals::default();
+    let mut missing_item_ids = Vec::new();
+    let mut incomplete_item_ids = Vec::new();
+    for (item_id, quantity) in entries {
+        let counted = nutrition
+            .iter()
+            .find(|facts| facts.item_id == *item_id)
+            .and_then(|facts| Some((facts, facts.portions(quantity)?)));
+        match counted {
+            Some((facts, portions)) => {
+                totals.add(facts, portions);
+                if !facts.is_complete() && !incomplete_item_ids.contains(item_id) {
+                    incomplete_item_ids.push(*item_id);
+                }
+            }
+            None if !missing_item_ids.contains(item_id) => missing_item_ids.push(*item_id),
+            None => {}
+        }
+    }
+    NutritionSummary {
+        totals,
+        missing_item_ids,
+        incomplete_item_ids,
+    }
+}
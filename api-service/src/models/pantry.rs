@@ -0,0 +1,288 @@
+use crate::schema::pantry_item::dsl::pantry_item as all_pantry_items;
+use crate::schema::pantry_threshold::dsl::pantry_threshold as all_thresholds;
+use crate::schema::{pantry_item, pantry_threshold};
+use chrono::NaiveDate;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use diesel::sql_types::VarChar;
+use diesel_enum::DbEnum;
+
+use super::unit::{Quantity, Unit};
+
+/// Amounts smaller than this are treated as nothing left, to absorb rounding from
+/// unit conversions.
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+#[derive(Debug)]
+pub struct StorageLocationError {
+    pub msg: String,
+    pub status: u16,
+}
+
+impl StorageLocationError {
+    fn not_found(msg: String) -> Self {
+        Self { msg, status: 404 }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, DbEnum,
+)]
+#[sql_type = "VarChar"]
+#[error_fn = "StorageLocationError::not_found"]
+#[error_type = "StorageLocationError"]
+pub enum StorageLocation {
+    Fridge,
+    Freezer,
+    Cupboard,
+    Other,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct PantryItem {
+    pub id: i32,
+    pub user_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+    pub location: StorageLocation,
+    pub expiry_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "pantry_item"]
+pub struct NewPantryItem {
+    pub user_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+    pub location: StorageLocation,
+    pub expiry_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, Insertable)]
+#[table_name = "pantry_threshold"]
+pub struct PantryThreshold {
+    pub user_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Debug)]
+pub enum ConsumeError {
+    NotEnoughStock,
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for ConsumeError {
+    fn from(err: diesel::result::Error) -> Self {
+        ConsumeError::Database(err)
+    }
+}
+
+/// Total of `quantities` expressed in `unit`, skipping any that cannot be converted.
+fn total_in_unit<I>(quantities: I, unit: Unit) -> f64
+where
+    I: IntoIterator<Item = Quantity>,
+{
+    quantities
+        .into_iter()
+        .filter_map(|quantity| quantity.convert_to(unit).ok())
+        .map(|quantity| quantity.amount)
+        .sum()
+}
+
+impl PantryItem {
+    pub fn quantity(&self) -> Quantity {
+        Quantity::new(self.quantity, self.unit)
+    }
+
+    pub fn get_pantry(conn: &PgConnection, user_id: i32) -> Vec<PantryItem> {
+        all_pantry_items
+            .filter(pantry_item::user_id.eq(user_id))
+            .order((pantry_item::item_id.asc(), pantry_item::expiry_date.asc()))
+            .load::<PantryItem>(conn)
+            .expect("Error loading pantry")
+    }
+
+    /// Stock of a single item, soonest to expire first and undated stock last.
+    pub fn get_item_stock(
+        conn: &PgConnection,
+        user_id: i32,
+        item_id: i32,
+    ) -> Result<Vec<PantryItem>, diesel::result::Error> {
+        all_pantry_items
+            .filter(pantry_item::user_id.eq(user_id))
+            .filter(pantry_item::item_id.eq(item_id))
+            .order((pantry_item::expiry_date.asc(), pantry_item::id.asc()))
+            .load::<PantryItem>(conn)
+    }
+
+    /// Stock with a best-before date on or before `until`, soonest first. Stock that
+    /// has already expired is included.
+    pub fn get_expiring(conn: &PgConnection, user_id: i32, until: NaiveDate) -> Vec<PantryItem> {
+        all_pantry_items
+            .filter(pantry_item::user_id.eq(user_id))
+            .filter(pantry_item::expiry_date.le(until))
+            .order((pantry_item::expiry_date.asc(), pantry_item::id.asc()))
+            .load::<PantryItem>(conn)
+            .expect("Error loading pantry")
+    }
+
+    /// Takes the user's pantry stock off `needed`, leaving only what still has to be
+    /// bought. Items that are fully in stock are dropped.
+    pub fn subtract_stock(
+        conn: &PgConnection,
+        user_id: i32,
+        needed: Vec<(i32, Quantity)>,
+    ) -> Vec<(i32, Quantity)> {
+        let mut stock: Vec<(i32, Quantity)> = PantryItem::get_pantry(conn, user_id)
+            .iter()
+            .map(|item| (item.item_id, item.quantity()))
+            .collect();
+        let mut to_buy = Vec::new();
+        for (item_id, mut quantity) in needed {
+            for (_, available) in stock.iter_mut().filter(|(id, _)| *id == item_id) {
+                let in_stock = match available.convert_to(quantity.unit) {
+                    Ok(in_stock) => in_stock,
+                    Err(_) => continue,
+                };
+                let used = Quantity::new(in_stock.amount.min(quantity.amount), quantity.unit);
+                if let Ok(used_from_stock) = used.convert_to(available.unit) {
+                    available.amount -= used_from_stock.amount;
+                    quantity.amount -= used.amount;
+                }
+            }
+            if quantity.amount > QUANTITY_EPSILON {
+                to_buy.push((item_id, quantity));
+            }
+        }
+        to_buy
+    }
+
+    pub fn get_pantry_item_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<PantryItem, diesel::result::Error> {
+        all_pantry_items.find(id).first::<PantryItem>(conn)
+    }
+
+    pub fn insert_pantry_item(conn: &PgConnection, item: &NewPantryItem) -> bool {
+        diesel::insert_into(pantry_item::table)
+            .values(item)
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn delete_pantry_item(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(pantry_item::table)
+            .filter(pantry_item::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn get_last_inserted_pantry_item(
+        conn: &PgConnection,
+    ) -> Result<PantryItem, diesel::result::Error> {
+        all_pantry_items
+            .order(pantry_item::id.desc())
+            .first::<PantryItem>(conn)
+    }
+
+    /// Removes `quantity` of an item from the pantry, using up the stock that expires
+    /// soonest first. Nothing is removed if there is not enough in stock.
+    pub fn consume(
+        conn: &PgConnection,
+        user_id: i32,
+        item_id: i32,
+        quantity: Quantity,
+    ) -> Result<Vec<PantryItem>, ConsumeError> {
+        conn.transaction(|| {
+            let mut remaining = quantity;
+            for stock in PantryItem::get_item_stock(conn, user_id, item_id)? {
+                if remaining.amount <= QUANTITY_EPSILON {
+                    break;
+                }
+                let wanted = match remaining.convert_to(stock.unit) {
+                    Ok(wanted) => wanted,
+                    Err(_) => continue,
+                };
+                if stock.quantity <= wanted.amount + QUANTITY_EPSILON {
+                    diesel::delete(pantry_item::table)
+                        .filter(pantry_item::id.eq(stock.id))
+                        .execute(conn)?;
+                    let used = stock
+                        .quantity()
+                        .convert_to(remaining.unit)
+                        .map_err(|_| ConsumeError::NotEnoughStock)?;
+                    remaining.amount -= used.amount;
+                } else {
+                    diesel::update(pantry_item::table)
+                        .filter(pantry_item::id.eq(stock.id))
+                        .set(pantry_item::quantity.eq(stock.quantity - wanted.amount))
+                        .execute(conn)?;
+                    remaining.amount = 0.0;
+                }
+            }
+            if remaining.amount > QUANTITY_EPSILON {
+                return Err(ConsumeError::NotEnoughStock);
+            }
+            PantryItem::get_item_stock(conn, user_id, item_id).map_err(ConsumeError::from)
+        })
+    }
+}
+
+impl PantryThreshold {
+    pub fn get_thresholds(conn: &PgConnection, user_id: i32) -> Vec<PantryThreshold> {
+        all_thresholds
+            .filter(pantry_threshold::user_id.eq(user_id))
+            .load::<PantryThreshold>(conn)
+            .expect("Error loading pantry thresholds")
+    }
+
+    /// Thresholds whose item has less in stock than the threshold, paired with how much
+    /// is left in the threshold's unit.
+    pub fn get_low_stock(conn: &PgConnection, user_id: i32) -> Vec<(PantryThreshold, f64)> {
+        let pantry = PantryItem::get_pantry(conn, user_id);
+        PantryThreshold::get_thresholds(conn, user_id)
+            .into_iter()
+            .map(|threshold| {
+                let in_stock = total_in_unit(
+                    pantry
+                        .iter()
+                        .filter(|stock| stock.item_id == threshold.item_id)
+                        .map(|stock| stock.quantity()),
+                    threshold.unit,
+                );
+                (threshold, in_stock)
+            })
+            .filter(|(threshold, in_stock)| *in_stock < threshold.quantity)
+            .collect()
+    }
+
+    pub fn upsert_threshold(conn: &PgConnection, threshold: &PantryThreshold) -> bool {
+        diesel::insert_into(pantry_threshold::table)
+            .values(threshold)
+            .on_conflict((pantry_threshold::user_id, pantry_threshold::item_id))
+            .do_update()
+            .set((
+                pantry_threshold::quantity.eq(threshold.quantity),
+                pantry_threshold::unit.eq(threshold.unit),
+            ))
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn delete_threshold(conn: &PgConnection, user_id: i32, item_id: i32) -> bool {
+        diesel::delete(pantry_threshold::table)
+            .filter(pantry_threshold::user_id.eq(user_id))
+            .filter(pantry_threshold::item_id.eq(item_id))
+            .execute(conn)
+            .is_ok()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::schema::item_price;
+use crate::schema::item_price::dsl::item_price as all_prices;
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::unit::{Quantity, Unit};
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct ItemPrice {
+    pub id: i32,
+    pub item_id: i32,
+    pub store_id: i32,
+    pub reporter_id: i32,
+    pub price_cents: i32,
+    pub currency: String,
+    pub quantity: f64,
+    pub unit: Unit,
+    pub observed_at: NaiveDateTime,
+    pub variant_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "item_price"]
+pub struct NewItemPrice {
+    pub item_id: i32,
+    pub store_id: i32,
+    pub reporter_id: i32,
+    pub price_cents: i32,
+    pub currency: String,
+    pub quantity: f64,
+    pub unit: Unit,
+    pub observed_at: NaiveDateTime,
+    pub variant_id: Option<i32>,
+}
+
+pub fn is_valid_currency(currency: &str) -> bool {
+    currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase())
+}
+
+impl ItemPrice {
+    pub fn quantity(&self) -> Quantity {
+        Quantity::new(self.quantity, self.unit)
+    }
+
+    /// Price in cents per base unit (gram, millilitre or single item), or `None` when the
+    /// observation is in a unit without a fixed size such as a pack.
+    pub fn unit_price(&self) -> Option<f64> {
+        let base_unit = Unit::base_unit(self.unit.unit_type());
+        match self.quantity().convert_to(base_unit) {
+            Ok(base) if base.amount > 0.0 => Some(self.price_cents as f64 / base.amount),
+            _ => None,
+        }
+    }
+
+    /// The most recent observation for each product at each store that has a price for
+    /// the item. Prices of the generic item and of each variant are kept apart.
+    pub fn get_latest_prices(conn: &PgConnection, item_id: i32) -> Vec<ItemPrice> {
+        all_prices
+            .filter(item_price::item_id.eq(item_id))
+            .distinct_on((item_price::store_id, item_price::variant_id))
+            .order((
+                item_price::store_id,
+                item_price::variant_id,
+                item_price::observed_at.desc(),
+            ))
+            .load::<ItemPrice>(conn)
+            .expect("Error loading prices")
+    }
+
+    /// The store whose latest price in `currency` is lowest per base unit. Prices in
+    /// other currencies are left out since they cannot be compared.
+    pub fn get_cheapest_price(
+        conn: &PgConnection,
+        item_id: i32,
+        currency: &str,
+    ) -> Option<ItemPrice> {
+        ItemPrice::get_latest_prices(conn, item_id)
+            .into_iter()
+            .filter(|price| price.currency == currency)
+            .filter_map(|price| price.unit_price().map(|unit_price| (unit_price, price)))
+            .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, price)| price)
+    }
+
+    pub fn get_price_history(
+        conn: &PgConnection,
+        item_id: i32,
+        store_id: Option<i32>,
+        variant_id: Option<i32>,
+    ) -> Vec<ItemPrice> {
+        let mut query = all_prices
+            .filter(item_price::item_id.eq(item_id))
+            .into_boxed();
+        if let Some(store) = store_id {
+            query = query.filter(item_price::store_id.eq(store));
+        }
+        if let Some(variant) = variant_id {
+            query = query.filter(item_price::variant_id.eq(variant));
+        }
+        query
+            .order(item_price::observed_at.asc())
+            .load::<ItemPrice>(conn)
+            .expect("Error loading prices")
+    }
+
+    pub fn insert_price(conn: &PgConnection, price: &NewItemPrice) -> bool {
+        diesel::insert_into(item_price::table)
+            .values(price)
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn get_last_inserted_price(
+        conn: &PgConnection,
+    ) -> Result<ItemPrice, diesel::result::Error> {
+        all_prices
+            .order(item_price::id.desc())
+            .first::<ItemPrice>(conn)
+    }
+}
@@ -0,0 +1,187 @@
+use crate::schema::item_proposal::dsl::item_proposal as all_proposals;
+use crate::schema::{item_proposal, shopping_item};
+use chrono::{NaiveDateTime, Utc};
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use diesel::sql_types::VarChar;
+use diesel_enum::DbEnum;
+
+use super::alias::{ItemAlias, NewItemAlias};
+use super::item::{NewShoppingItem, ShoppingItem, UnitType};
+
+#[derive(Debug)]
+pub struct ProposalStatusError {
+    pub msg: String,
+    pub status: u16,
+}
+
+impl ProposalStatusError {
+    fn not_found(msg: String) -> Self {
+        Self { msg, status: 404 }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, DbEnum,
+)]
+#[sql_type = "VarChar"]
+#[error_fn = "ProposalStatusError::not_found"]
+#[error_type = "ProposalStatusError"]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Merged,
+}
+
+/// A user's suggestion for a new catalog item, or for changes to an existing one when
+/// `item_id` is set, waiting for an admin to review it.
+#[derive(Debug, Serialize, Queryable)]
+pub struct ItemProposal {
+    pub id: i32,
+    pub proposed_by: i32,
+    pub item_id: Option<i32>,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub default_unit_type: UnitType,
+    pub status: ProposalStatus,
+    pub rejection_reason: Option<String>,
+    pub resolved_item_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub reviewed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Insertable)]
+#[table_name = "item_proposal"]
+pub struct NewItemProposal {
+    pub proposed_by: i32,
+    pub item_id: Option<i32>,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub default_unit_type: UnitType,
+}
+
+impl ItemProposal {
+    pub fn get_user_proposals(conn: &PgConnection, user_id: i32) -> Vec<ItemProposal> {
+        all_proposals
+            .filter(item_proposal::proposed_by.eq(user_id))
+            .order(item_proposal::created_at.desc())
+            .load::<ItemProposal>(conn)
+            .expect("Error loading proposals")
+    }
+
+    /// The moderation queue, oldest first.
+    pub fn get_pending_proposals(conn: &PgConnection) -> Vec<ItemProposal> {
+        all_proposals
+            .filter(item_proposal::status.eq(ProposalStatus::Pending))
+            .order(item_proposal::created_at.asc())
+            .load::<ItemProposal>(conn)
+            .expect("Error loading proposals")
+    }
+
+    pub fn get_proposal_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<ItemProposal, diesel::result::Error> {
+        all_proposals.find(id).first::<ItemProposal>(conn)
+    }
+
+    pub fn insert_proposal(
+        conn: &PgConnection,
+        proposal: &NewItemProposal,
+    ) -> Result<ItemProposal, diesel::result::Error> {
+        diesel::insert_into(item_proposal::table)
+            .values(proposal)
+            .get_result::<ItemProposal>(conn)
+    }
+
+    fn resolve(
+        conn: &PgConnection,
+        id: i32,
+        status: ProposalStatus,
+        resolved_item_id: Option<i32>,
+        rejection_reason: Option<&str>,
+    ) -> Result<ItemProposal, diesel::result::Error> {
+        diesel::update(item_proposal::table)
+            .filter(item_proposal::id.eq(id))
+            .set((
+                item_proposal::status.eq(status),
+                item_proposal::resolved_item_id.eq(resolved_item_id),
+                item_proposal::rejection_reason.eq(rejection_reason),
+                item_proposal::reviewed_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .get_result::<ItemProposal>(conn)
+    }
+
+    /// Adds the proposed item to the catalog, or applies the proposed changes to the
+    /// existing item for an edit.
+    pub fn approve(&self, conn: &PgConnection) -> Result<ItemProposal, diesel::result::Error> {
+        let item = NewShoppingItem {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            image_url: self.image_url.clone(),
+            default_unit_type: self.default_unit_type,
+            owner_id: None,
+        };
+        conn.transaction(|| {
+            let item_id = match self.item_id {
+                Some(item_id) => {
+                    diesel::update(shopping_item::table)
+                        .filter(shopping_item::id.eq(item_id))
+                        .set((
+                            shopping_item::name.eq(&item.name),
+                            shopping_item::description.eq(&item.description),
+                            shopping_item::image_url.eq(&item.image_url),
+                            shopping_item::default_unit_type.eq(item.default_unit_type),
+                        ))
+                        .execute(conn)?;
+                    item_id
+                }
+                None => {
+                    diesel::insert_into(shopping_item::table)
+                        .values(&item)
+                        .get_result::<ShoppingItem>(conn)?
+                        .id
+                }
+            };
+            ItemProposal::resolve(conn, self.id, ProposalStatus::Approved, Some(item_id), None)
+        })
+    }
+
+    pub fn reject(
+        &self,
+        conn: &PgConnection,
+        reason: &str,
+    ) -> Result<ItemProposal, diesel::result::Error> {
+        ItemProposal::resolve(conn, self.id, ProposalStatus::Rejected, None, Some(reason))
+    }
+
+    /// Resolves a new-item proposal as a duplicate of an existing item, keeping the
+    /// proposed name as an alias of it so it can still be found.
+    pub fn merge_into(
+        &self,
+        conn: &PgConnection,
+        item: &ShoppingItem,
+    ) -> Result<ItemProposal, diesel::result::Error> {
+        conn.transaction(|| {
+            if !self.name.eq_ignore_ascii_case(&item.name)
+                && ItemAlias::get_alias_by_name(conn, &self.name).is_err()
+            {
+                ItemAlias::insert_alias(
+                    conn,
+                    &NewItemAlias {
+                        item_id: item.id,
+                        alias: self.name.clone(),
+                    },
+                )?;
+            }
+            ItemProposal::resolve(conn, self.id, ProposalStatus::Merged, Some(item.id), None)
+        })
+    }
+}
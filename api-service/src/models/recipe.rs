@@ -0,0 +1,162 @@
+use crate::schema::recipe::dsl::recipe as all_recipes;
+use crate::schema::recipe_ingredient::dsl::recipe_ingredient as all_ingredients;
+use crate::schema::{recipe, recipe_ingredient};
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::unit::{aggregate_by_item, Quantity, Unit};
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct Recipe {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub servings: i32,
+    pub steps: Vec<String>,
+    pub created_by: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable, AsChangeset)]
+#[table_name = "recipe"]
+pub struct NewRecipe {
+    pub name: String,
+    pub description: Option<String>,
+    pub servings: i32,
+    pub steps: Vec<String>,
+    pub created_by: i32,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct RecipeIngredient {
+    pub id: i32,
+    pub recipe_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "recipe_ingredient"]
+pub struct NewRecipeIngredient {
+    pub recipe_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecipeDetails {
+    #[serde(flatten)]
+    pub recipe: Recipe,
+    pub ingredients: Vec<RecipeIngredient>,
+}
+
+impl RecipeIngredient {
+    pub fn quantity(&self) -> Quantity {
+        Quantity::new(self.quantity, self.unit)
+    }
+}
+
+impl RecipeDetails {
+    /// Ingredient quantities for the requested number of servings, with repeated items
+    /// merged into a single quantity where their units allow it.
+    pub fn scaled_ingredients(&self, servings: i32) -> Vec<(i32, Quantity)> {
+        let factor = servings as f64 / self.recipe.servings as f64;
+        aggregate_by_item(
+            self.ingredients
+                .iter()
+                .map(|ingredient| (ingredient.item_id, ingredient.quantity().scale(factor))),
+        )
+    }
+}
+
+impl Recipe {
+    pub fn get_all_recipes(conn: &PgConnection) -> Vec<Recipe> {
+        all_recipes
+            .order(recipe::id.desc())
+            .load::<Recipe>(conn)
+            .expect("Error loading recipes")
+    }
+
+    pub fn get_recipe_by_id(conn: &PgConnection, id: i32) -> Result<Recipe, diesel::result::Error> {
+        all_recipes.find(id).first::<Recipe>(conn)
+    }
+
+    pub fn get_recipe_details(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<RecipeDetails, diesel::result::Error> {
+        let recipe = Recipe::get_recipe_by_id(conn, id)?;
+        let ingredients = all_ingredients
+            .filter(recipe_ingredient::recipe_id.eq(id))
+            .order(recipe_ingredient::id.asc())
+            .load::<RecipeIngredient>(conn)?;
+        Ok(RecipeDetails {
+            recipe,
+            ingredients,
+        })
+    }
+
+    fn insert_ingredients(
+        conn: &PgConnection,
+        recipe_id: i32,
+        ingredients: &[(i32, Quantity)],
+    ) -> Result<usize, diesel::result::Error> {
+        let rows: Vec<NewRecipeIngredient> = ingredients
+            .iter()
+            .map(|(item_id, quantity)| NewRecipeIngredient {
+                recipe_id,
+                item_id: *item_id,
+                quantity: quantity.amount,
+                unit: quantity.unit,
+            })
+            .collect();
+        diesel::insert_into(recipe_ingredient::table)
+            .values(&rows)
+            .execute(conn)
+    }
+
+    pub fn insert_recipe(
+        conn: &PgConnection,
+        recipe: &NewRecipe,
+        ingredients: &[(i32, Quantity)],
+    ) -> Result<RecipeDetails, diesel::result::Error> {
+        conn.transaction(|| {
+            let inserted = diesel::insert_into(recipe::table)
+                .values(recipe)
+                .get_result::<Recipe>(conn)?;
+            Recipe::insert_ingredients(conn, inserted.id, ingredients)?;
+            Recipe::get_recipe_details(conn, inserted.id)
+        })
+    }
+
+    /// Replaces a recipe's details and its full ingredient list.
+    pub fn update_recipe(
+        conn: &PgConnection,
+        id: i32,
+        recipe: &NewRecipe,
+        ingredients: &[(i32, Quantity)],
+    ) -> Result<RecipeDetails, diesel::result::Error> {
+        conn.transaction(|| {
+            diesel::update(recipe::table)
+                .filter(recipe::id.eq(id))
+                .set(recipe)
+                .execute(conn)?;
+            diesel::delete(recipe_ingredient::table)
+                .filter(recipe_ingredient::recipe_id.eq(id))
+                .execute(conn)?;
+            Recipe::insert_ingredients(conn, id, ingredients)?;
+            Recipe::get_recipe_details(conn, id)
+        })
+    }
+
+    pub fn delete_recipe(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(recipe::table)
+            .filter(recipe::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+}
@@ -0,0 +1,132 @@
+use crate::schema::user_dietary_restriction;
+use crate::schema::user_dietary_restriction::dsl::user_dietary_restriction as all_restrictions;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use diesel::sql_types::VarChar;
+use diesel_enum::DbEnum;
+
+use super::item::{ItemVisibility, ShoppingItem};
+use super::nutrition::ItemNutrition;
+
+#[derive(Debug)]
+pub struct DietaryRestrictionError {
+    pub msg: String,
+    pub status: u16,
+}
+
+impl DietaryRestrictionError {
+    fn not_found(msg: String) -> Self {
+        Self { msg, status: 404 }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, DbEnum,
+)]
+#[sql_type = "VarChar"]
+#[error_fn = "DietaryRestrictionError::not_found"]
+#[error_type = "DietaryRestrictionError"]
+pub enum DietaryRestriction {
+    Vegan,
+    GlutenFree,
+    NutFree,
+}
+
+impl DietaryRestriction {
+    /// Whether the item is known to break this restriction. Items without the relevant
+    /// flag are not reported.
+    pub fn is_violated_by(&self, nutrition: &ItemNutrition) -> bool {
+        match self {
+            DietaryRestriction::Vegan => nutrition.vegan == Some(false),
+            DietaryRestriction::GlutenFree => nutrition.gluten_free == Some(false),
+            DietaryRestriction::NutFree => nutrition.contains_nuts == Some(true),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Queryable, Insertable)]
+#[table_name = "user_dietary_restriction"]
+pub struct UserDietaryRestriction {
+    pub user_id: i32,
+    pub restriction: DietaryRestriction,
+}
+
+/// An item that breaks one of the user's dietary restrictions.
+#[derive(Debug, Serialize)]
+pub struct DietaryConflict {
+    pub item_id: i32,
+    pub item_name: String,
+    pub restriction: DietaryRestriction,
+}
+
+impl UserDietaryRestriction {
+    pub fn get_restrictions(conn: &PgConnection, user_id: i32) -> Vec<DietaryRestriction> {
+        all_restrictions
+            .filter(user_dietary_restriction::user_id.eq(user_id))
+            .order(user_dietary_restriction::restriction.asc())
+            .load::<UserDietaryRestriction>(conn)
+            .expect("Error loading dietary restrictions")
+            .into_iter()
+            .map(|row| row.restriction)
+            .collect()
+    }
+
+    /// Replaces the user's full set of restrictions.
+    pub fn set_restrictions(
+        conn: &PgConnection,
+        user_id: i32,
+        restrictions: &[DietaryRestriction],
+    ) -> Result<Vec<DietaryRestriction>, diesel::result::Error> {
+        let rows: Vec<UserDietaryRestriction> = restrictions
+            .iter()
+            .map(|restriction| UserDietaryRestriction {
+                user_id,
+                restriction: *restriction,
+            })
+            .collect();
+        conn.transaction(|| {
+            diesel::delete(user_dietary_restriction::table)
+                .filter(user_dietary_restriction::user_id.eq(user_id))
+                .execute(conn)?;
+            diesel::insert_into(user_dietary_restriction::table)
+                .values(&rows)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            Ok(UserDietaryRestriction::get_restrictions(conn, user_id))
+        })
+    }
+
+    /// Lists the items among `item_ids` that break any of the user's restrictions.
+    pub fn find_conflicts(
+        conn: &PgConnection,
+        user_id: i32,
+        item_ids: &[i32],
+    ) -> Vec<DietaryConflict> {
+        let restrictions = UserDietaryRestriction::get_restrictions(conn, user_id);
+        if restrictions.is_empty() || item_ids.is_empty() {
+            return Vec::new();
+        }
+        let nutrition = ItemNutrition::get_items_nutrition(conn, item_ids);
+        let items = ShoppingItem::get_items_by_ids(conn, item_ids, ItemVisibility::User(user_id));
+        items
+            .iter()
+            .flat_map(|item| {
+                let facts = nutrition.iter().find(|facts| facts.item_id == item.id);
+                restrictions
+                    .iter()
+                    .filter(move |restriction| {
+                        facts.map_or(false, |f| restriction.is_violated_by(f))
+                    })
+                    .map(move |restriction| DietaryConflict {
+                        item_id: item.id,
+                        item_name: item.name.clone(),
+                        restriction: *restriction,
+                    })
+            })
+            .collect()
+    }
+}
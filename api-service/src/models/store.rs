@@ -0,0 +1,115 @@
+use crate::schema::store;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{Double, Int4, Nullable, VarChar};
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+const STORE_COLUMNS: &str = "id, name, address, \
+    ST_Y(location::geometry) AS latitude, ST_X(location::geometry) AS longitude";
+
+#[derive(SqlType)]
+#[postgres(type_name = "geography")]
+pub struct Geography;
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct Store {
+    #[sql_type = "Int4"]
+    pub id: i32,
+    #[sql_type = "VarChar"]
+    pub name: String,
+    #[sql_type = "Nullable<VarChar>"]
+    pub address: Option<String>,
+    #[sql_type = "Double"]
+    pub latitude: f64,
+    #[sql_type = "Double"]
+    pub longitude: f64,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct NearbyStore {
+    #[diesel(embed)]
+    #[serde(flatten)]
+    pub store: Store,
+    #[sql_type = "Double"]
+    pub distance_m: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewStore {
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+pub fn is_valid_location(latitude: f64, longitude: f64) -> bool {
+    (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)
+}
+
+impl Store {
+    pub fn get_all_stores(conn: &PgConnection) -> Vec<Store> {
+        diesel::sql_query(format!(
+            "SELECT {} FROM store ORDER BY id DESC",
+            STORE_COLUMNS
+        ))
+        .load::<Store>(conn)
+        .expect("Error loading stores")
+    }
+
+    pub fn get_store_by_id(conn: &PgConnection, id: i32) -> Result<Store, diesel::result::Error> {
+        diesel::sql_query(format!("SELECT {} FROM store WHERE id = $1", STORE_COLUMNS))
+            .bind::<Int4, _>(id)
+            .get_result::<Store>(conn)
+    }
+
+    /// Stores within `radius` metres of the given point, closest first.
+    pub fn get_nearby_stores(
+        conn: &PgConnection,
+        latitude: f64,
+        longitude: f64,
+        radius: f64,
+    ) -> Vec<NearbyStore> {
+        diesel::sql_query(format!(
+            "SELECT {}, ST_Distance(location, origin) AS distance_m \
+             FROM store, (SELECT ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography AS origin) o \
+             WHERE ST_DWithin(location, origin, $3) \
+             ORDER BY distance_m ASC",
+            STORE_COLUMNS
+        ))
+        .bind::<Double, _>(longitude)
+        .bind::<Double, _>(latitude)
+        .bind::<Double, _>(radius)
+        .load::<NearbyStore>(conn)
+        .expect("Error loading stores")
+    }
+
+    pub fn insert_store(conn: &PgConnection, store: &NewStore) -> bool {
+        diesel::sql_query(
+            "INSERT INTO store (name, address, location) \
+             VALUES ($1, $2, ST_SetSRID(ST_MakePoint($3, $4), 4326)::geography)",
+        )
+        .bind::<VarChar, _>(&store.name)
+        .bind::<Nullable<VarChar>, _>(&store.address)
+        .bind::<Double, _>(store.longitude)
+        .bind::<Double, _>(store.latitude)
+        .execute(conn)
+        .is_ok()
+    }
+
+    pub fn delete_store(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(store::table)
+            .filter(store::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+
+    pub fn get_last_inserted_store(conn: &PgConnection) -> Result<Store, diesel::result::Error> {
+        diesel::sql_query(format!(
+            "SELECT {} FROM store ORDER BY id DESC LIMIT 1",
+            STORE_COLUMNS
+        ))
+        .get_result::<Store>(conn)
+    }
+}
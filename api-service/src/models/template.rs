@@ -0,0 +1,138 @@
+use crate::schema::list_template::dsl::list_template as all_templates;
+use crate::schema::list_template_item::dsl::list_template_item as all_template_items;
+use crate::schema::{list_template, list_template_item};
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::unit::{Quantity, Unit};
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct ListTemplate {
+    pub id: i32,
+    pub name: String,
+    pub created_by: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable, AsChangeset)]
+#[table_name = "list_template"]
+pub struct NewListTemplate {
+    pub name: String,
+    pub created_by: i32,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+pub struct ListTemplateItem {
+    pub id: i32,
+    pub template_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "list_template_item"]
+pub struct NewListTemplateItem {
+    pub template_id: i32,
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTemplateDetails {
+    #[serde(flatten)]
+    pub template: ListTemplate,
+    pub items: Vec<ListTemplateItem>,
+}
+
+impl ListTemplate {
+    pub fn get_templates_for_user(conn: &PgConnection, user_id: i32) -> Vec<ListTemplate> {
+        all_templates
+            .filter(list_template::created_by.eq(user_id))
+            .order(list_template::name.asc())
+            .load::<ListTemplate>(conn)
+            .expect("Error loading templates")
+    }
+
+    pub fn get_template_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<ListTemplate, diesel::result::Error> {
+        all_templates.find(id).first::<ListTemplate>(conn)
+    }
+
+    pub fn get_template_details(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<ListTemplateDetails, diesel::result::Error> {
+        let template = ListTemplate::get_template_by_id(conn, id)?;
+        let items = all_template_items
+            .filter(list_template_item::template_id.eq(id))
+            .order(list_template_item::id.asc())
+            .load::<ListTemplateItem>(conn)?;
+        Ok(ListTemplateDetails { template, items })
+    }
+
+    fn insert_items(
+        conn: &PgConnection,
+        template_id: i32,
+        items: &[(i32, Quantity)],
+    ) -> Result<usize, diesel::result::Error> {
+        let rows: Vec<NewListTemplateItem> = items
+            .iter()
+            .map(|(item_id, quantity)| NewListTemplateItem {
+                template_id,
+                item_id: *item_id,
+                quantity: quantity.amount,
+                unit: quantity.unit,
+            })
+            .collect();
+        diesel::insert_into(list_template_item::table)
+            .values(&rows)
+            .execute(conn)
+    }
+
+    pub fn insert_template(
+        conn: &PgConnection,
+        template: &NewListTemplate,
+        items: &[(i32, Quantity)],
+    ) -> Result<ListTemplateDetails, diesel::result::Error> {
+        conn.transaction(|| {
+            let inserted = diesel::insert_into(list_template::table)
+                .values(template)
+                .get_result::<ListTemplate>(conn)?;
+            ListTemplate::insert_items(conn, inserted.id, items)?;
+            ListTemplate::get_template_details(conn, inserted.id)
+        })
+    }
+
+    /// Replaces a template's name and its full set of items.
+    pub fn update_template(
+        conn: &PgConnection,
+        id: i32,
+        template: &NewListTemplate,
+        items: &[(i32, Quantity)],
+    ) -> Result<ListTemplateDetails, diesel::result::Error> {
+        conn.transaction(|| {
+            diesel::update(list_template::table)
+                .filter(list_template::id.eq(id))
+                .set(template)
+                .execute(conn)?;
+            diesel::delete(list_template_item::table)
+                .filter(list_template_item::template_id.eq(id))
+                .execute(conn)?;
+            ListTemplate::insert_items(conn, id, items)?;
+            ListTemplate::get_template_details(conn, id)
+        })
+    }
+
+    pub fn delete_template(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(list_template::table)
+            .filter(list_template::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::schema::item_translation;
+use crate::schema::item_translation::dsl::item_translation as all_translations;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::item::ShoppingItem;
+
+#[derive(Debug, Serialize, Deserialize, Queryable, Insertable)]
+#[table_name = "item_translation"]
+pub struct ItemTranslation {
+    pub item_id: i32,
+    pub locale: String,
+    pub name: String,
+}
+
+impl ItemTranslation {
+    pub fn get_item_translations(conn: &PgConnection, item_id: i32) -> Vec<ItemTranslation> {
+        all_translations
+            .filter(item_translation::item_id.eq(item_id))
+            .order(item_translation::locale.asc())
+            .load::<ItemTranslation>(conn)
+            .expect("Error loading translations")
+    }
+
+    pub fn get_translation(
+        conn: &PgConnection,
+        item_id: i32,
+        locale: &str,
+    ) -> Result<ItemTranslation, diesel::result::Error> {
+        all_translations
+            .find((item_id, locale))
+            .first::<ItemTranslation>(conn)
+    }
+
+    /// Inserts the item's name for a locale, replacing any existing one.
+    pub fn upsert_translation(
+        conn: &PgConnection,
+        translation: &ItemTranslation,
+    ) -> Result<ItemTranslation, diesel::result::Error> {
+        diesel::insert_into(item_translation::table)
+            .values(translation)
+            .on_conflict((item_translation::item_id, item_translation::locale))
+            .do_update()
+            .set(item_translation::name.eq(&translation.name))
+            .get_result::<ItemTranslation>(conn)
+    }
+
+    pub fn delete_translation(conn: &PgConnection, item_id: i32, locale: &str) -> bool {
+        diesel::delete(item_translation::table)
+            .filter(item_translation::item_id.eq(item_id))
+            .filter(item_translation::locale.eq(locale))
+            .execute(conn)
+            .is_ok()
+    }
+
+    /// Replaces each item's name with its translation in the most preferred of `locales`,
+    /// leaving items without a matching translation untouched.
+    pub fn localize_items(conn: &PgConnection, items: &mut [ShoppingItem], locales: &[String]) {
+        if items.is_empty() || locales.is_empty() {
+            return;
+        }
+        let ids: Vec<i32> = items.iter().map(|item| item.id).collect();
+        let translations = all_translations
+            .filter(item_translation::item_id.eq_any(ids))
+            .filter(item_translation::locale.eq_any(locales))
+            .load::<ItemTranslation>(conn)
+            .expect("Error loading translations");
+        for item in items.iter_mut() {
+            let preferred = translations
+                .iter()
+                .filter(|translation| translation.item_id == item.id)
+                .min_by_key(|translation| {
+                    locales
+                        .iter()
+                        .position(|locale| *locale == translation.locale)
+                });
+            if let Some(translation) = preferred {
+                item.name = translation.name.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt;
+
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use diesel::sql_types::VarChar;
+use diesel_enum::DbEnum;
+
+use super::item::UnitType;
+
+#[derive(Debug)]
+pub struct UnitError {
+    pub msg: String,
+    pub status: u16,
+}
+
+impl UnitError {
+    fn not_found(msg: String) -> Self {
+        Self { msg, status: 404 }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, DbEnum,
+)]
+#[sql_type = "VarChar"]
+#[error_fn = "UnitError::not_found"]
+#[error_type = "UnitError"]
+pub enum Unit {
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+    Millilitre,
+    Litre,
+    Teaspoon,
+    Tablespoon,
+    Cup,
+    Each,
+    Dozen,
+    Pack,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 12] = [
+        Unit::Gram,
+        Unit::Kilogram,
+        Unit::Ounce,
+        Unit::Pound,
+        Unit::Millilitre,
+        Unit::Litre,
+        Unit::Teaspoon,
+        Unit::Tablespoon,
+        Unit::Cup,
+        Unit::Each,
+        Unit::Dozen,
+        Unit::Pack,
+    ];
+
+    pub fn unit_type(&self) -> UnitType {
+        match self {
+            Unit::Gram | Unit::Kilogram | Unit::Ounce | Unit::Pound => UnitType::Mass,
+            Unit::Millilitre | Unit::Litre | Unit::Teaspoon | Unit::Tablespoon | Unit::Cup => {
+                UnitType::Capacity
+            }
+            Unit::Each | Unit::Dozen | Unit::Pack => UnitType::Count,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Ounce => "oz",
+            Unit::Pound => "lb",
+            Unit::Millilitre => "ml",
+            Unit::Litre => "L",
+            Unit::Teaspoon => "tsp",
+            Unit::Tablespoon => "tbsp",
+            Unit::Cup => "cup",
+            Unit::Each => "each",
+            Unit::Dozen => "dozen",
+            Unit::Pack => "pack",
+        }
+    }
+
+    /// Recognises a unit written by hand, accepting the symbol as well as the usual
+    /// singular, plural and abbreviated spellings, case insensitively.
+    pub fn from_symbol(symbol: &str) -> Option<Unit> {
+        match symbol.trim().trim_end_matches('.').to_lowercase().as_str() {
+            "g" | "gr" | "gram" | "grams" => Some(Unit::Gram),
+            "kg" | "kilo" | "kilos" | "kilogram" | "kilograms" => Some(Unit::Kilogram),
+            "oz" | "ounce" | "ounces" => Some(Unit::Ounce),
+            "lb" | "lbs" | "pound" | "pounds" => Some(Unit::Pound),
+            "ml" | "millilitre" | "millilitres" | "milliliter" | "milliliters" => {
+                Some(Unit::Millilitre)
+            }
+            "l" | "litre" | "litres" | "liter" | "liters" => Some(Unit::Litre),
+            "tsp" | "teaspoon" | "teaspoons" => Some(Unit::Teaspoon),
+            "tbsp" | "tablespoon" | "tablespoons" => Some(Unit::Tablespoon),
+            "cup" | "cups" => Some(Unit::Cup),
+            "each" | "pc" | "pcs" | "piece" | "pieces" => Some(Unit::Each),
+            "dozen" | "doz" => Some(Unit::Dozen),
+            "pack" | "packs" | "pk" | "packet" | "packets" => Some(Unit::Pack),
+            _ => None,
+        }
+    }
+
+    /// The base unit every other unit of the same type converts through.
+    pub fn base_unit(unit_type: UnitType) -> Unit {
+        match unit_type {
+            UnitType::Mass => Unit::Gram,
+            UnitType::Capacity => Unit::Millilitre,
+            UnitType::Count => Unit::Each,
+        }
+    }
+
+    /// How many base units one of this unit is worth. A pack has no fixed size so it
+    /// only converts to itself.
+    fn base_factor(&self) -> Option<f64> {
+        match self {
+            Unit::Gram => Some(1.0),
+            Unit::Kilogram => Some(1000.0),
+            Unit::Ounce => Some(28.349523125),
+            Unit::Pound => Some(453.59237),
+            Unit::Millilitre => Some(1.0),
+            Unit::Litre => Some(1000.0),
+            Unit::Teaspoon => Some(4.92892159375),
+            Unit::Tablespoon => Some(14.78676478125),
+            Unit::Cup => Some(236.5882365),
+            Unit::Each => Some(1.0),
+            Unit::Dozen => Some(12.0),
+            Unit::Pack => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantityError {
+    IncompatibleUnits(Unit, Unit),
+    WrongUnitType(Unit, UnitType),
+}
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantityError::IncompatibleUnits(from, to) => {
+                write!(f, "Cannot convert {} to {}", from.symbol(), to.symbol())
+            }
+            QuantityError::WrongUnitType(unit, unit_type) => write!(
+                f,
+                "Unit {} cannot be used for a {:?} item",
+                unit.symbol(),
+                unit_type
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub amount: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    pub fn new(amount: f64, unit: Unit) -> Self {
+        Self { amount, unit }
+    }
+
+    pub fn convert_to(&self, unit: Unit) -> Result<Quantity, QuantityError> {
+        if self.unit == unit {
+            return Ok(*self);
+        }
+        let incompatible = QuantityError::IncompatibleUnits(self.unit, unit);
+        if self.unit.unit_type() != unit.unit_type() {
+            return Err(incompatible);
+        }
+        match (self.unit.base_factor(), unit.base_factor()) {
+            (Some(from), Some(to)) => Ok(Quantity::new(self.amount * from / to, unit)),
+            _ => Err(incompatible),
+        }
+    }
+
+    /// Adds two quantities, keeping the unit of `self`.
+    pub fn add(&self, other: &Quantity) -> Result<Quantity, QuantityError> {
+        let converted = other.convert_to(self.unit)?;
+        Ok(Quantity::new(self.amount + converted.amount, self.unit))
+    }
+
+    pub fn scale(&self, factor: f64) -> Quantity {
+        Quantity::new(self.amount * factor, self.unit)
+    }
+
+    /// Checks the quantity is measured in a unit an item of `unit_type` can use.
+    pub fn check_unit_type(&self, unit_type: UnitType) -> Result<(), QuantityError> {
+        if self.unit.unit_type() == unit_type {
+            Ok(())
+        } else {
+            Err(QuantityError::WrongUnitType(self.unit, unit_type))
+        }
+    }
+}
+
+/// Merges quantities of the same item, converting each into the unit the item was first
+/// seen in. Quantities that cannot be converted (e.g. packs and single items) are kept as
+/// separate entries.
+pub fn aggregate_by_item<I>(entries: I) -> Vec<(i32, Quantity)>
+where
+    I: IntoIterator<Item = (i32, Quantity)>,
+{
+    let mut merged: Vec<(i32, Quantity)> = Vec::new();
+    for (item_id, quantity) in entries {
+        let existing = merged
+            .iter_mut()
+            .find(|(id, total)| *id == item_id && quantity.convert_to(total.unit).is_ok());
+        match existing {
+            Some((_, total)) => *total = total.add(&quantity).unwrap_or(*total),
+            None => merged.push((item_id, quantity)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_amount(quantity: Quantity, amount: f64, unit: Unit) {
+        assert_eq!(quantity.unit, unit);
+        assert!(
+            (quantity.amount - amount).abs() < 1e-9,
+            "expected {} {}, got {}",
+            amount,
+            unit.symbol(),
+            quantity.amount
+        );
+    }
+
+    #[test]
+    fn converts_between_grams_and_kilograms() {
+        let grams = Quantity::new(1500.0, Unit::Gram);
+        assert_amount(
+            grams.convert_to(Unit::Kilogram).unwrap(),
+            1.5,
+            Unit::Kilogram,
+        );
+        let kilograms = Quantity::new(0.25, Unit::Kilogram);
+        assert_amount(kilograms.convert_to(Unit::Gram).unwrap(), 250.0, Unit::Gram);
+    }
+
+    #[test]
+    fn converts_between_millilitres_and_litres() {
+        let millilitres = Quantity::new(750.0, Unit::Millilitre);
+        assert_amount(
+            millilitres.convert_to(Unit::Litre).unwrap(),
+            0.75,
+            Unit::Litre,
+        );
+        let litres = Quantity::new(2.0, Unit::Litre);
+        assert_amount(
+            litres.convert_to(Unit::Millilitre).unwrap(),
+            2000.0,
+            Unit::Millilitre,
+        );
+    }
+
+    #[test]
+    fn converts_imperial_and_kitchen_units() {
+        let pound = Quantity::new(1.0, Unit::Pound);
+        assert_amount(pound.convert_to(Unit::Ounce).unwrap(), 16.0, Unit::Ounce);
+        let cup = Quantity::new(1.0, Unit::Cup);
+        assert_amount(
+            cup.convert_to(Unit::Teaspoon).unwrap(),
+            48.0,
+            Unit::Teaspoon,
+        );
+    }
+
+    #[test]
+    fn rejects_converting_mass_to_capacity() {
+        let grams = Quantity::new(100.0, Unit::Gram);
+        assert_eq!(
+            grams.convert_to(Unit::Millilitre),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::Gram,
+                Unit::Millilitre
+            ))
+        );
+    }
+
+    #[test]
+    fn packs_only_convert_to_themselves() {
+        let pack = Quantity::new(2.0, Unit::Pack);
+        assert!(pack.convert_to(Unit::Each).is_err());
+        assert_amount(pack.convert_to(Unit::Pack).unwrap(), 2.0, Unit::Pack);
+    }
+
+    #[test]
+    fn aggregates_the_same_item_in_compatible_units() {
+        let merged = aggregate_by_item(vec![
+            (1, Quantity::new(500.0, Unit::Gram)),
+            (2, Quantity::new(1.0, Unit::Litre)),
+            (1, Quantity::new(1.5, Unit::Kilogram)),
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].0, 1);
+        assert_amount(merged[0].1, 2000.0, Unit::Gram);
+        assert_eq!(merged[1].0, 2);
+        assert_amount(merged[1].1, 1.0, Unit::Litre);
+    }
+
+    #[test]
+    fn keeps_incompatible_quantities_of_an_item_apart() {
+        let merged = aggregate_by_item(vec![
+            (1, Quantity::new(2.0, Unit::Pack)),
+            (1, Quantity::new(3.0, Unit::Each)),
+        ]);
+        assert_eq!(merged.len(), 2);
+    }
+}
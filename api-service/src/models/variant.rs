@@ -0,0 +1,128 @@
+use crate::schema::item_variant;
+use crate::schema::item_variant::dsl::item_variant as all_variants;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::Deserialize;
+use serde_derive::Serialize;
+
+use super::item::MAX_IMAGE_URL_LENGTH;
+use super::unit::{Quantity, Unit};
+
+const MAX_BRAND_LENGTH: usize = 128;
+const PACKAGE_SIZE_TOLERANCE: f64 = 1e-6;
+
+/// A specific product sold under a generic item, such as a brand's 1 l carton of milk.
+#[derive(Debug, Serialize, Queryable)]
+pub struct ItemVariant {
+    pub id: i32,
+    pub item_id: i32,
+    pub brand: String,
+    pub package_quantity: f64,
+    pub package_unit: Unit,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "item_variant"]
+pub struct NewItemVariant {
+    pub item_id: i32,
+    pub brand: String,
+    pub package_quantity: f64,
+    pub package_unit: Unit,
+    pub image_url: Option<String>,
+}
+
+impl NewItemVariant {
+    /// Checks a brand is given, the package size is positive and the image URL is not
+    /// too long.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.brand.trim().is_empty() || self.brand.len() > MAX_BRAND_LENGTH {
+            return Err(format!(
+                "brand must be between 1 and {} characters",
+                MAX_BRAND_LENGTH
+            ));
+        }
+        if self.package_quantity <= 0.0 {
+            return Err(String::from("package_quantity must be positive"));
+        }
+        if self
+            .image_url
+            .as_ref()
+            .map_or(false, |url| url.len() > MAX_IMAGE_URL_LENGTH)
+        {
+            return Err(format!(
+                "image_url must be at most {} characters",
+                MAX_IMAGE_URL_LENGTH
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn package_size(&self) -> Quantity {
+        Quantity::new(self.package_quantity, self.package_unit)
+    }
+}
+
+impl ItemVariant {
+    pub fn package_size(&self) -> Quantity {
+        Quantity::new(self.package_quantity, self.package_unit)
+    }
+
+    /// Whether `quantity` is one package of this variant, allowing for rounding in
+    /// unit conversions.
+    pub fn is_package_size(&self, quantity: &Quantity) -> bool {
+        quantity
+            .convert_to(self.package_unit)
+            .map_or(false, |converted| {
+                (converted.amount - self.package_quantity).abs()
+                    <= PACKAGE_SIZE_TOLERANCE * self.package_quantity
+            })
+    }
+
+    pub fn get_item_variants(conn: &PgConnection, item_id: i32) -> Vec<ItemVariant> {
+        all_variants
+            .filter(item_variant::item_id.eq(item_id))
+            .order((
+                item_variant::brand.asc(),
+                item_variant::package_quantity.asc(),
+            ))
+            .load::<ItemVariant>(conn)
+            .expect("Error loading variants")
+    }
+
+    pub fn get_variant_by_id(
+        conn: &PgConnection,
+        id: i32,
+    ) -> Result<ItemVariant, diesel::result::Error> {
+        all_variants.find(id).first::<ItemVariant>(conn)
+    }
+
+    /// Looks up a variant only if it belongs to the given item.
+    pub fn get_item_variant(
+        conn: &PgConnection,
+        item_id: i32,
+        id: i32,
+    ) -> Result<ItemVariant, diesel::result::Error> {
+        all_variants
+            .filter(item_variant::id.eq(id))
+            .filter(item_variant::item_id.eq(item_id))
+            .first::<ItemVariant>(conn)
+    }
+
+    pub fn insert_variant(
+        conn: &PgConnection,
+        variant: &NewItemVariant,
+    ) -> Result<ItemVariant, diesel::result::Error> {
+        diesel::insert_into(item_variant::table)
+            .values(variant)
+            .get_result::<ItemVariant>(conn)
+    }
+
+    pub fn delete_variant(conn: &PgConnection, id: i32) -> bool {
+        diesel::delete(item_variant::table)
+            .filter(item_variant::id.eq(id))
+            .execute(conn)
+            .is_ok()
+    }
+}
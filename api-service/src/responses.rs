@@ -32,6 +32,20 @@ pub fn success_response(data: Value) -> JsonResponse {
     }
 }
 
+/// A successful response that also carries non-fatal warnings about the data, such as
+/// items that clash with the user's dietary restrictions.
+pub fn success_response_with_warnings(data: Value, warnings: Value) -> JsonResponse {
+    JsonResponse {
+        status: Status::Ok,
+        message: json!({
+          "success": true,
+          "error": json!(null),
+          "data": data,
+          "warnings": warnings,
+        }),
+    }
+}
+
 pub fn error_response(status: Status, error: &str) -> JsonResponse {
     JsonResponse {
         status: status,
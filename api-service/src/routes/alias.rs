@@ -0,0 +1,95 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{AdminRequest, OptionalUserRequest},
+    models::alias::{ItemAlias, NewItemAlias},
+    models::item::{ItemVisibility, ShoppingItem},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+const MAX_ALIAS_LENGTH: usize = 128;
+
+#[derive(Deserialize)]
+pub struct NewAliasData {
+    pub alias: String,
+}
+
+#[get("/items/<item_id>/aliases")]
+pub fn get_item_aliases(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        success_response(json!(ItemAlias::get_item_aliases(
+            &req.state.connection,
+            item_id
+        )))
+    })
+}
+
+#[post("/items/<item_id>/aliases", data = "<new_alias>")]
+pub fn post_item_alias(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    new_alias: Option<Json<NewAliasData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_alias {
+            Some(alias_data) => {
+                let alias = alias_data.alias.trim();
+                if alias.is_empty() || alias.len() > MAX_ALIAS_LENGTH {
+                    return error_response(Status::BadRequest, "Invalid alias");
+                }
+                if ShoppingItem::get_item_by_id(&req.state.connection, item_id, ItemVisibility::All)
+                    .is_err()
+                {
+                    return error_response(Status::NotFound, "Item not found");
+                }
+                if ItemAlias::get_alias_by_name(&req.state.connection, alias).is_ok() {
+                    return error_response(Status::Conflict, "Alias already in use");
+                }
+                let result = ItemAlias::insert_alias(
+                    &req.state.connection,
+                    &NewItemAlias {
+                        item_id,
+                        alias: String::from(alias),
+                    },
+                );
+                match result {
+                    Ok(alias) => success_response(json!(alias)),
+                    Err(_) => error_response(Status::InternalServerError, "Failed to insert alias"),
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse alias data"),
+        }
+    })
+}
+
+#[delete("/items/<item_id>/aliases/<alias_id>")]
+pub fn delete_item_alias(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    alias_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req: AdminRequest| -> JsonResponse {
+        match ItemAlias::get_alias_by_id(&req.state.connection, alias_id) {
+            Ok(alias) if alias.item_id == item_id => {
+                let success = ItemAlias::delete_alias(&req.state.connection, alias_id);
+                if success {
+                    success_response(json!(alias))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete alias")
+                }
+            }
+            _ => error_response(Status::NotFound, "Alias not found"),
+        }
+    })
+}
@@ -0,0 +1,135 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{AdminRequest, OptionalUserRequest},
+    locale::AcceptLanguage,
+    models::barcode::{normalize_gtin, ItemBarcode},
+    models::item::{ItemVisibility, ShoppingItem},
+    models::translation::ItemTranslation,
+    models::variant::ItemVariant,
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct NewBarcodeData {
+    pub code: String,
+    pub variant_id: Option<i32>,
+}
+
+// Ranked after the `/items/<item_id>/...` routes, which share the same shape.
+#[get("/items/by-barcode/<code>", rank = 2)]
+pub fn get_item_by_barcode(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    languages: AcceptLanguage,
+    code: String,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        match normalize_gtin(&code) {
+            Some(gtin) => {
+                match ItemBarcode::get_item_by_gtin(&req.state.connection, &gtin, visibility) {
+                    Ok((mut item, variant)) => {
+                        ItemTranslation::localize_items(
+                            &req.state.connection,
+                            std::slice::from_mut(&mut item),
+                            &languages.0,
+                        );
+                        let mut response = json!(item);
+                        response["variant"] = json!(variant);
+                        success_response(response)
+                    }
+                    Err(_) => error_response(Status::NotFound, "No item with this barcode"),
+                }
+            }
+            None => error_response(Status::BadRequest, "Invalid barcode"),
+        }
+    })
+}
+
+#[get("/items/<item_id>/barcodes")]
+pub fn get_item_barcodes(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        success_response(json!(ItemBarcode::get_item_barcodes(
+            &req.state.connection,
+            item_id
+        )))
+    })
+}
+
+#[post("/items/<item_id>/barcodes", data = "<new_barcode>")]
+pub fn post_item_barcode(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    new_barcode: Option<Json<NewBarcodeData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_barcode {
+            Some(barcode_data) => {
+                let gtin = match normalize_gtin(&barcode_data.code) {
+                    Some(gtin) => gtin,
+                    None => return error_response(Status::BadRequest, "Invalid barcode"),
+                };
+                if ShoppingItem::get_item_by_id(&req.state.connection, item_id, ItemVisibility::All)
+                    .is_err()
+                {
+                    return error_response(Status::NotFound, "Item not found");
+                }
+                if let Some(variant_id) = barcode_data.variant_id {
+                    if ItemVariant::get_item_variant(&req.state.connection, item_id, variant_id)
+                        .is_err()
+                    {
+                        return error_response(Status::NotFound, "Variant not found");
+                    }
+                }
+                if ItemBarcode::get_barcode(&req.state.connection, &gtin).is_ok() {
+                    return error_response(Status::Conflict, "Barcode already in use");
+                }
+                let barcode = ItemBarcode {
+                    gtin,
+                    item_id,
+                    variant_id: barcode_data.variant_id,
+                };
+                if ItemBarcode::insert_barcode(&req.state.connection, &barcode) {
+                    success_response(json!(barcode))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to insert barcode")
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse barcode data"),
+        }
+    })
+}
+
+#[delete("/items/<item_id>/barcodes/<code>")]
+pub fn delete_item_barcode(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    code: String,
+) -> JsonResponse {
+    handle_request(request, |req: AdminRequest| -> JsonResponse {
+        let barcode = normalize_gtin(&code)
+            .and_then(|gtin| ItemBarcode::get_barcode(&req.state.connection, &gtin).ok());
+        match barcode {
+            Some(barcode) if barcode.item_id == item_id => {
+                let success = ItemBarcode::delete_barcode(&req.state.connection, &barcode.gtin);
+                if success {
+                    success_response(json!(barcode))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete barcode")
+                }
+            }
+            _ => error_response(Status::NotFound, "Barcode not found"),
+        }
+    })
+}
@@ -0,0 +1,73 @@
+use std::io::Read;
+
+use rocket::http::{ContentType, Status};
+use rocket::response::content::Content;
+use rocket::Data;
+
+use crate::{
+    auth::AdminRequest,
+    catalog::{export_catalog, import_catalog, parse_rows, CatalogFormat},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+const MAX_IMPORT_SIZE: u64 = 10 * 1024 * 1024;
+
+fn parse_format(format: Option<String>) -> Result<CatalogFormat, JsonResponse> {
+    match format {
+        Some(name) => CatalogFormat::from_name(&name)
+            .ok_or_else(|| error_response(Status::BadRequest, "Unknown catalog format")),
+        None => Ok(CatalogFormat::Json),
+    }
+}
+
+#[get("/items/export?<format>")]
+pub fn get_catalog_export(
+    request: Result<AdminRequest, JsonResponse>,
+    format: Option<String>,
+) -> Result<Content<String>, JsonResponse> {
+    let req = request?;
+    let format = parse_format(format)?;
+    let body = export_catalog(&req.state.connection, format)
+        .map_err(|_| error_response(Status::InternalServerError, "Failed to export catalog"))?;
+    let content_type = match format {
+        CatalogFormat::Csv => ContentType::CSV,
+        CatalogFormat::Json => ContentType::JSON,
+    };
+    Ok(Content(content_type, body))
+}
+
+#[post("/items/import?<format>&<dry_run>", data = "<data>")]
+pub fn post_catalog_import(
+    request: Result<AdminRequest, JsonResponse>,
+    format: Option<String>,
+    dry_run: Option<bool>,
+    data: Data,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let format = match parse_format(format) {
+            Ok(format) => format,
+            Err(err) => return err,
+        };
+        let mut body = String::new();
+        if data
+            .open()
+            .take(MAX_IMPORT_SIZE + 1)
+            .read_to_string(&mut body)
+            .is_err()
+        {
+            return error_response(Status::BadRequest, "Import file must be valid UTF-8");
+        }
+        if body.len() as u64 > MAX_IMPORT_SIZE {
+            return error_response(Status::PayloadTooLarge, "Import file is too large");
+        }
+        let rows = match parse_rows(format, &body) {
+            Ok(rows) => rows,
+            Err(err) => return error_response(Status::BadRequest, &err),
+        };
+        match import_catalog(&req.state.connection, rows, dry_run.unwrap_or(false)) {
+            Ok(report) => success_response(json!(report)),
+            Err(_) => error_response(Status::InternalServerError, "Failed to import catalog"),
+        }
+    })
+}
@@ -0,0 +1,105 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::AdminRequest,
+    images::delete_item_images,
+    models::duplicate::{find_duplicates, merge_items},
+    models::item::{ItemVisibility, ShoppingItem},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.4;
+const DEFAULT_DUPLICATE_LIMIT: i64 = 50;
+const MAX_DUPLICATE_LIMIT: i64 = 200;
+
+#[derive(Deserialize)]
+pub struct MergeData {
+    pub duplicate_ids: Vec<i32>,
+}
+
+#[get("/items/duplicates?<threshold>&<limit>")]
+pub fn get_duplicate_items(
+    request: Result<AdminRequest, JsonResponse>,
+    threshold: Option<f64>,
+    limit: Option<i64>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let threshold = threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return error_response(Status::BadRequest, "Threshold must be between 0 and 1");
+        }
+        let limit = limit
+            .unwrap_or(DEFAULT_DUPLICATE_LIMIT)
+            .clamp(1, MAX_DUPLICATE_LIMIT);
+        success_response(json!(find_duplicates(
+            &req.state.connection,
+            threshold,
+            limit
+        )))
+    })
+}
+
+/// Merges the given duplicates into `item_id`, re-pointing everything that referenced
+/// them before deleting them.
+#[post("/items/<item_id>/merge", data = "<merge_data>")]
+pub fn post_merge_items(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    merge_data: Option<Json<MergeData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let mut duplicate_ids = match &merge_data {
+            Some(data) => data.duplicate_ids.clone(),
+            None => return error_response(Status::BadRequest, "Failed to parse merge data"),
+        };
+        duplicate_ids.sort_unstable();
+        duplicate_ids.dedup();
+        if duplicate_ids.is_empty() || duplicate_ids.contains(&item_id) {
+            return error_response(
+                Status::BadRequest,
+                "Duplicates must be other items than the one merged into",
+            );
+        }
+        let item = match ShoppingItem::get_item_by_id(
+            &req.state.connection,
+            item_id,
+            ItemVisibility::Global,
+        ) {
+            Ok(item) => item,
+            Err(_) => return error_response(Status::NotFound, "Item not found"),
+        };
+        let duplicates = ShoppingItem::get_items_by_ids(
+            &req.state.connection,
+            &duplicate_ids,
+            ItemVisibility::Global,
+        );
+        if duplicates.len() != duplicate_ids.len() {
+            return error_response(Status::NotFound, "Duplicate item not found");
+        }
+        if duplicates
+            .iter()
+            .any(|duplicate| duplicate.default_unit_type != item.default_unit_type)
+        {
+            return error_response(
+                Status::BadRequest,
+                "Cannot merge items measured in different unit types",
+            );
+        }
+
+        match merge_items(&req.state.connection, item_id, &duplicate_ids) {
+            Ok(()) => {
+                for duplicate_id in duplicate_ids {
+                    if !delete_item_images(req.state.blob_store, duplicate_id) {
+                        println!("Failed to clean up images for item {}", duplicate_id);
+                    }
+                }
+                success_response(json!(item))
+            }
+            Err(_) => error_response(Status::InternalServerError, "Failed to merge items"),
+        }
+    })
+}
@@ -1,27 +1,58 @@
-use rocket::http::Status;
+use std::io::Read;
+
+use multipart::server::Multipart;
+use rocket::http::{ContentType, Status};
+use rocket::response::content::Content;
+use rocket::Data;
+use serde_derive::Deserialize;
 
 use crate::{
-    auth::{AdminRequest, PublicRequest, UserRequest},
-    models::item::{NewShoppingItem, ShoppingItem},
+    auth::{AdminRequest, OptionalUserRequest, UserRequest},
+    images::{
+        delete_item_images, item_image_url, load_item_image, read_limited, store_item_image,
+        ImageSize, MAX_IMAGE_SIZE,
+    },
+    locale::AcceptLanguage,
+    models::item::{ItemVisibility, NewShoppingItem, ShoppingItem},
+    models::nutrition::DietaryFilter,
+    models::translation::ItemTranslation,
     responses::{error_response, success_response, JsonResponse},
+    text_import::import_text,
     utils::handle_request,
 };
 
 use rocket_contrib::json::Json;
 
-#[get("/items?<q>")]
+const IMAGE_FIELD_NAME: &str = "image";
+
+#[derive(Deserialize)]
+pub struct TextImportData {
+    pub text: String,
+}
+
+#[get("/items?<q>&<vegan>&<gluten_free>&<nut_free>")]
 pub fn get_all_items(
-    request: Result<PublicRequest, JsonResponse>,
+    request: Result<OptionalUserRequest, JsonResponse>,
+    languages: AcceptLanguage,
     q: Option<String>,
+    vegan: Option<bool>,
+    gluten_free: Option<bool>,
+    nut_free: Option<bool>,
 ) -> JsonResponse {
     handle_request(request, |req| -> JsonResponse {
-        match q {
-            Some(query) => success_response(json!(ShoppingItem::search_all_items(
-                &req.state.connection,
-                query.as_str()
-            ))),
-            None => success_response(json!(ShoppingItem::get_all_items(&req.state.connection))),
-        }
+        let dietary = DietaryFilter {
+            vegan: vegan.unwrap_or(false),
+            gluten_free: gluten_free.unwrap_or(false),
+            nut_free: nut_free.unwrap_or(false),
+        };
+        let mut items = ShoppingItem::find_items(
+            &req.state.connection,
+            q.as_deref(),
+            dietary,
+            ItemVisibility::for_user(req.user_id()),
+        );
+        ItemTranslation::localize_items(&req.state.connection, &mut items, &languages.0);
+        success_response(json!(items))
     })
 }
 
@@ -40,21 +71,87 @@ pub fn post_new_item(
                         description: item_data.description.clone(),
                         image_url: item_data.image_url.clone(),
                         default_unit_type: item_data.default_unit_type.clone(),
+                        owner_id: None,
                     },
                 );
-                if result {
-                    let item = ShoppingItem::get_last_inserted_item(&req.state.connection);
-                    match item {
-                        Ok(inserted_item) => success_response(json!(inserted_item)),
-                        Err(_) => {
-                            error_response(Status::InternalServerError, "Failed to insert item")
-                        }
+                match result {
+                    Ok(inserted_item) => success_response(json!(inserted_item)),
+                    Err(_) => error_response(Status::InternalServerError, "Failed to insert item"),
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse new item data"),
+        }
+    })
+}
+
+/// Adds an item only the caller can see, for things that don't belong in the shared
+/// catalog.
+#[post("/items/private", data = "<new_item>")]
+pub fn post_private_item(
+    request: Result<UserRequest, JsonResponse>,
+    new_item: Option<Json<NewShoppingItem>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_item {
+            Some(item_data) => {
+                let item = NewShoppingItem {
+                    name: String::from(item_data.name.trim()),
+                    description: item_data.description.clone(),
+                    image_url: item_data.image_url.clone(),
+                    default_unit_type: item_data.default_unit_type,
+                    owner_id: Some(req.token.user_id),
+                };
+                if let Err(err) = item.validate() {
+                    return error_response(Status::BadRequest, &err);
+                }
+                match ShoppingItem::insert_item(&req.state.connection, &item) {
+                    Ok(inserted_item) => success_response(json!(inserted_item)),
+                    Err(_) => error_response(Status::InternalServerError, "Failed to insert item"),
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse new item data"),
+        }
+    })
+}
+
+#[delete("/items/private/<item_id>")]
+pub fn delete_private_item(
+    request: Result<UserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let visibility = ItemVisibility::User(req.token.user_id);
+        match ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility) {
+            Ok(item) if item.owner_id == Some(req.token.user_id) => {
+                if ShoppingItem::delete_item(&req.state.connection, item_id) {
+                    if !delete_item_images(req.state.blob_store, item_id) {
+                        println!("Failed to clean up images for item {}", item_id);
                     }
+                    success_response(json!(item))
                 } else {
-                    error_response(Status::InternalServerError, "Failed to insert item")
+                    error_response(Status::InternalServerError, "Failed to delete item")
                 }
             }
-            None => error_response(Status::BadRequest, "Failed to parse new item data"),
+            _ => error_response(Status::NotFound, "Item not found"),
+        }
+    })
+}
+
+/// Turns pasted free-form lines into item quantities, listing the lines that could not
+/// be matched against the catalog separately.
+#[post("/items/parse-text", data = "<text_data>")]
+pub fn post_parse_text(
+    request: Result<UserRequest, JsonResponse>,
+    text_data: Option<Json<TextImportData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &text_data {
+            Some(data) => success_response(json!(import_text(
+                &req.state.connection,
+                &data.text,
+                ItemVisibility::User(req.token.user_id)
+            ))),
+            None => error_response(Status::BadRequest, "Failed to parse text data"),
         }
     })
 }
@@ -62,11 +159,15 @@ pub fn post_new_item(
 #[delete("/items/<item_id>")]
 pub fn delete_item(request: Result<AdminRequest, JsonResponse>, item_id: i32) -> JsonResponse {
     handle_request(request, |req: AdminRequest| -> JsonResponse {
-        let potential_item = ShoppingItem::get_item_by_id(&req.state.connection, item_id);
+        let potential_item =
+            ShoppingItem::get_item_by_id(&req.state.connection, item_id, ItemVisibility::All);
         match potential_item {
             Ok(item) => {
                 let success = ShoppingItem::delete_item(&req.state.connection, item_id);
                 if success {
+                    if !delete_item_images(req.state.blob_store, item_id) {
+                        println!("Failed to clean up images for item {}", item_id);
+                    }
                     success_response(json!(item))
                 } else {
                     error_response(Status::InternalServerError, "Failed to delete user")
@@ -76,3 +177,80 @@ pub fn delete_item(request: Result<AdminRequest, JsonResponse>, item_id: i32) ->
         }
     })
 }
+
+#[get("/items/<item_id>/image?<size>")]
+pub fn get_item_image(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+    size: Option<String>,
+) -> Result<Content<Vec<u8>>, JsonResponse> {
+    let req = request?;
+    let visibility = ItemVisibility::for_user(req.user_id());
+    ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility)
+        .map_err(|_| error_response(Status::NotFound, "Item not found"))?;
+    let size = match size {
+        Some(name) => ImageSize::from_name(&name)
+            .ok_or_else(|| error_response(Status::BadRequest, "Unknown image size"))?,
+        None => ImageSize::Original,
+    };
+    match load_item_image(req.state.blob_store, item_id, size) {
+        Some((content_type, bytes)) => Ok(Content(content_type, bytes)),
+        None => Err(error_response(Status::NotFound, "Image not found")),
+    }
+}
+
+#[post("/items/<item_id>/image", data = "<data>")]
+pub fn post_item_image(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    content_type: &ContentType,
+    data: Data,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, ItemVisibility::All)
+            .is_err()
+        {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        let boundary = match content_type.params().find(|&(key, _)| key == "boundary") {
+            Some((_, boundary)) if content_type.is_form_data() => boundary,
+            _ => return error_response(Status::BadRequest, "Expected multipart form data"),
+        };
+
+        // Allow a little room for the multipart framing around the image itself.
+        let mut multipart = Multipart::with_body(data.open().take(MAX_IMAGE_SIZE * 2), boundary);
+        let mut image_bytes = None;
+        while let Ok(Some(field)) = multipart.read_entry() {
+            if &*field.headers.name == IMAGE_FIELD_NAME {
+                image_bytes = Some(read_limited(field.data));
+                break;
+            }
+        }
+
+        let stored = match image_bytes {
+            Some(Ok(bytes)) => store_item_image(req.state.blob_store, item_id, &bytes),
+            Some(Err(err)) => Err(err),
+            None => return error_response(Status::BadRequest, "Missing image field"),
+        };
+        match stored {
+            Ok(()) => {
+                let image_url = item_image_url(item_id);
+                if ShoppingItem::set_image_url(&req.state.connection, item_id, Some(&image_url)) {
+                    match ShoppingItem::get_item_by_id(
+                        &req.state.connection,
+                        item_id,
+                        ItemVisibility::All,
+                    ) {
+                        Ok(item) => success_response(json!(item)),
+                        Err(_) => {
+                            error_response(Status::InternalServerError, "Failed to load item")
+                        }
+                    }
+                } else {
+                    error_response(Status::InternalServerError, "Failed to update item")
+                }
+            }
+            Err(err) => error_response(err.status(), err.message()),
+        }
+    })
+}
@@ -0,0 +1,168 @@
+use chrono::NaiveDate;
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::UserRequest,
+    models::item::{ItemVisibility, ShoppingItem},
+    models::meal_plan::{MealPlanEntry, MealSlot, NewMealPlanEntry},
+    models::recipe::Recipe,
+    responses::{error_response, success_response, JsonResponse},
+    utils::{handle_request, parse_date},
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct MealPlanData {
+    pub planned_for: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: i32,
+    pub servings: Option<i32>,
+}
+
+fn parse_date_range(from: &str, to: &str) -> Result<(NaiveDate, NaiveDate), JsonResponse> {
+    match (parse_date(from), parse_date(to)) {
+        (Some(from), Some(to)) if from <= to => Ok((from, to)),
+        (Some(_), Some(_)) => Err(error_response(
+            Status::BadRequest,
+            "Start date must not be after end date",
+        )),
+        _ => Err(error_response(
+            Status::BadRequest,
+            "Dates must be formatted as YYYY-MM-DD",
+        )),
+    }
+}
+
+#[get("/meal-plan?<from>&<to>")]
+pub fn get_meal_plan(
+    request: Result<UserRequest, JsonResponse>,
+    from: String,
+    to: String,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match parse_date_range(&from, &to) {
+            Ok((from, to)) => success_response(json!(MealPlanEntry::get_entries_between(
+                &req.state.connection,
+                req.token.user_id,
+                from,
+                to
+            ))),
+            Err(err) => err,
+        }
+    })
+}
+
+#[get("/meal-plan/ingredients?<from>&<to>")]
+pub fn get_meal_plan_ingredients(
+    request: Result<UserRequest, JsonResponse>,
+    from: String,
+    to: String,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let (from, to) = match parse_date_range(&from, &to) {
+            Ok(range) => range,
+            Err(err) => return err,
+        };
+        let ingredients = MealPlanEntry::get_ingredients_between(
+            &req.state.connection,
+            req.token.user_id,
+            from,
+            to,
+        );
+        match ingredients {
+            Ok(ingredients) => {
+                let item_ids: Vec<i32> = ingredients.iter().map(|(item_id, _)| *item_id).collect();
+                let items = ShoppingItem::get_items_by_ids(
+                    &req.state.connection,
+                    &item_ids,
+                    ItemVisibility::User(req.token.user_id),
+                );
+                let result: Vec<_> = items
+                    .iter()
+                    .flat_map(|item| {
+                        ingredients
+                            .iter()
+                            .filter(move |(item_id, _)| *item_id == item.id)
+                            .map(move |(_, quantity)| json!({ "item": item, "quantity": quantity }))
+                    })
+                    .collect();
+                success_response(json!(result))
+            }
+            Err(_) => error_response(Status::InternalServerError, "Failed to load recipes"),
+        }
+    })
+}
+
+#[post("/meal-plan", data = "<new_entry>")]
+pub fn post_meal_plan_entry(
+    request: Result<UserRequest, JsonResponse>,
+    new_entry: Option<Json<MealPlanData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_entry {
+            Some(entry_data) => {
+                let recipe =
+                    match Recipe::get_recipe_by_id(&req.state.connection, entry_data.recipe_id) {
+                        Ok(recipe) => recipe,
+                        Err(_) => return error_response(Status::NotFound, "Recipe not found"),
+                    };
+                let servings = entry_data.servings.unwrap_or(recipe.servings);
+                if servings <= 0 {
+                    return error_response(Status::BadRequest, "Servings must be positive");
+                }
+                let result = MealPlanEntry::insert_entry(
+                    &req.state.connection,
+                    &NewMealPlanEntry {
+                        user_id: req.token.user_id,
+                        planned_for: entry_data.planned_for,
+                        meal_slot: entry_data.meal_slot,
+                        recipe_id: recipe.id,
+                        servings,
+                    },
+                );
+                if result {
+                    let entry = MealPlanEntry::get_last_inserted_entry(&req.state.connection);
+                    match entry {
+                        Ok(inserted_entry) => success_response(json!(inserted_entry)),
+                        Err(_) => error_response(
+                            Status::InternalServerError,
+                            "Failed to insert meal plan entry",
+                        ),
+                    }
+                } else {
+                    error_response(
+                        Status::InternalServerError,
+                        "Failed to insert meal plan entry",
+                    )
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse meal plan data"),
+        }
+    })
+}
+
+#[delete("/meal-plan/<entry_id>")]
+pub fn delete_meal_plan_entry(
+    request: Result<UserRequest, JsonResponse>,
+    entry_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let potential_entry = MealPlanEntry::get_entry_by_id(&req.state.connection, entry_id);
+        match potential_entry {
+            Ok(entry) if entry.user_id == req.token.user_id => {
+                let success = MealPlanEntry::delete_entry(&req.state.connection, entry_id);
+                if success {
+                    success_response(json!(entry))
+                } else {
+                    error_response(
+                        Status::InternalServerError,
+                        "Failed to delete meal plan entry",
+                    )
+                }
+            }
+            _ => error_response(Status::NotFound, "Meal plan entry not found"),
+        }
+    })
+}
@@ -1,4 +1,21 @@
+pub mod alias;
 pub mod auth;
+pub mod barcode;
+pub mod catalog;
+pub mod duplicate;
 pub mod item;
+pub mod meal_plan;
+pub mod notification;
+pub mod nutrition;
+pub mod pantry;
+pub mod price;
+pub mod proposal;
 pub mod public;
+pub mod recipe;
+pub mod restriction;
+pub mod store;
+pub mod template;
+pub mod translation;
+pub mod unit;
 pub mod users;
+pub mod variant;
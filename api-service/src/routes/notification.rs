@@ -0,0 +1,48 @@
+use rocket::http::Status;
+
+use crate::{
+    auth::UserRequest,
+    models::notification::Notification,
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+#[get("/notifications")]
+pub fn get_notifications(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(Notification::get_notifications(
+            &req.state.connection,
+            req.token.user_id
+        )))
+    })
+}
+
+#[patch("/notifications/<notification_id>/read")]
+pub fn patch_notification_read(
+    request: Result<UserRequest, JsonResponse>,
+    notification_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let potential_notification =
+            Notification::get_notification_by_id(&req.state.connection, notification_id);
+        match potential_notification {
+            Ok(notification) if notification.user_id == req.token.user_id => {
+                if Notification::mark_read(&req.state.connection, notification_id) {
+                    match Notification::get_notification_by_id(
+                        &req.state.connection,
+                        notification_id,
+                    ) {
+                        Ok(updated) => success_response(json!(updated)),
+                        Err(_) => error_response(
+                            Status::InternalServerError,
+                            "Failed to load notification",
+                        ),
+                    }
+                } else {
+                    error_response(Status::InternalServerError, "Failed to update notification")
+                }
+            }
+            _ => error_response(Status::NotFound, "Notification not found"),
+        }
+    })
+}
@@ -0,0 +1,137 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{OptionalUserRequest, UserRequest},
+    models::item::{ItemVisibility, ShoppingItem},
+    models::nutrition::{sum_nutrition, ItemNutrition},
+    models::recipe::Recipe,
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct NutritionData {
+    pub energy_kcal: Option<f64>,
+    pub protein_g: Option<f64>,
+    pub fat_g: Option<f64>,
+    pub carbohydrate_g: Option<f64>,
+    pub sugar_g: Option<f64>,
+    pub salt_g: Option<f64>,
+    pub fibre_g: Option<f64>,
+    pub unit_weight_g: Option<f64>,
+    pub vegan: Option<bool>,
+    pub gluten_free: Option<bool>,
+    pub contains_nuts: Option<bool>,
+}
+
+impl NutritionData {
+    fn is_valid(&self) -> bool {
+        let nutrients = [
+            self.energy_kcal,
+            self.protein_g,
+            self.fat_g,
+            self.carbohydrate_g,
+            self.sugar_g,
+            self.salt_g,
+            self.fibre_g,
+        ];
+        nutrients.iter().flatten().all(|value| *value >= 0.0)
+            && self.unit_weight_g.map_or(true, |weight| weight > 0.0)
+    }
+}
+
+#[get("/items/<item_id>/nutrition")]
+pub fn get_item_nutrition(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        match ItemNutrition::get_item_nutrition(&req.state.connection, item_id) {
+            Ok(nutrition) => success_response(json!(nutrition)),
+            Err(_) => error_response(Status::NotFound, "No nutrition facts for this item"),
+        }
+    })
+}
+
+/// Replaces an item's nutrition facts. Admins can edit any shared item; users can edit
+/// their own private items.
+#[post("/items/<item_id>/nutrition", data = "<nutrition>")]
+pub fn post_item_nutrition(
+    request: Result<UserRequest, JsonResponse>,
+    item_id: i32,
+    nutrition: Option<Json<NutritionData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::User(req.token.user_id);
+        match ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility) {
+            Ok(item) if req.token.is_admin || item.owner_id == Some(req.token.user_id) => {}
+            Ok(_) => return error_response(Status::Forbidden, "Admin access required"),
+            Err(_) => return error_response(Status::NotFound, "Item not found"),
+        }
+        match &nutrition {
+            Some(data) => {
+                if !data.is_valid() {
+                    return error_response(
+                        Status::BadRequest,
+                        "Nutrition values must not be negative",
+                    );
+                }
+                let result = ItemNutrition::upsert_nutrition(
+                    &req.state.connection,
+                    &ItemNutrition {
+                        item_id,
+                        energy_kcal: data.energy_kcal,
+                        protein_g: data.protein_g,
+                        fat_g: data.fat_g,
+                        carbohydrate_g: data.carbohydrate_g,
+                        sugar_g: data.sugar_g,
+                        salt_g: data.salt_g,
+                        fibre_g: data.fibre_g,
+                        unit_weight_g: data.unit_weight_g,
+                        vegan: data.vegan,
+                        gluten_free: data.gluten_free,
+                        contains_nuts: data.contains_nuts,
+                    },
+                );
+                match result {
+                    Ok(nutrition) => success_response(json!(nutrition)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to save nutrition")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse nutrition data"),
+        }
+    })
+}
+
+#[get("/recipes/<recipe_id>/nutrition?<servings>")]
+pub fn get_recipe_nutrition(
+    request: Result<UserRequest, JsonResponse>,
+    recipe_id: i32,
+    servings: Option<i32>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match Recipe::get_recipe_details(&req.state.connection, recipe_id) {
+            Ok(details) => {
+                let servings = servings.unwrap_or(details.recipe.servings);
+                if servings <= 0 {
+                    return error_response(Status::BadRequest, "Servings must be positive");
+                }
+                let ingredients = details.scaled_ingredients(servings);
+                success_response(json!({
+                    "servings": servings,
+                    "nutrition": sum_nutrition(&req.state.connection, &ingredients),
+                }))
+            }
+            Err(_) => error_response(Status::NotFound, "Recipe not found"),
+        }
+    })
+}
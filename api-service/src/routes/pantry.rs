@@ -0,0 +1,256 @@
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::pg::PgConnection;
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::UserRequest,
+    models::item::{ItemQuantityError, ItemVisibility, ShoppingItem},
+    models::pantry::{ConsumeError, NewPantryItem, PantryItem, PantryThreshold, StorageLocation},
+    models::unit::{Quantity, Unit},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+const DEFAULT_EXPIRING_WITHIN_DAYS: i64 = 7;
+const MAX_EXPIRING_WITHIN_DAYS: i64 = 3650;
+
+#[derive(Deserialize)]
+pub struct PantryItemData {
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+    pub location: StorageLocation,
+    pub expiry_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+pub struct ItemQuantityData {
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+/// Checks the item exists and can be measured in the given quantity's unit.
+fn validate_item_quantity(
+    conn: &PgConnection,
+    visibility: ItemVisibility,
+    item_id: i32,
+    quantity: &Quantity,
+) -> Result<(), JsonResponse> {
+    ShoppingItem::validate_quantity(conn, item_id, quantity, visibility).map_err(|err| match err {
+        ItemQuantityError::NotPositive => {
+            error_response(Status::BadRequest, "Quantity must be positive")
+        }
+        ItemQuantityError::ItemNotFound => error_response(Status::NotFound, "Item not found"),
+        ItemQuantityError::WrongUnit(err) => error_response(Status::BadRequest, &err.to_string()),
+    })
+}
+
+#[get("/pantry")]
+pub fn get_pantry(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(PantryItem::get_pantry(
+            &req.state.connection,
+            req.token.user_id
+        )))
+    })
+}
+
+#[get("/pantry/low-stock")]
+pub fn get_low_stock(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let low_stock: Vec<_> =
+            PantryThreshold::get_low_stock(&req.state.connection, req.token.user_id)
+                .into_iter()
+                .map(|(threshold, in_stock)| {
+                    json!({
+                        "item_id": threshold.item_id,
+                        "threshold": Quantity::new(threshold.quantity, threshold.unit),
+                        "in_stock": Quantity::new(in_stock, threshold.unit),
+                    })
+                })
+                .collect();
+        success_response(json!(low_stock))
+    })
+}
+
+#[get("/pantry/expiring?<within_days>")]
+pub fn get_expiring(
+    request: Result<UserRequest, JsonResponse>,
+    within_days: Option<i64>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let within_days = within_days.unwrap_or(DEFAULT_EXPIRING_WITHIN_DAYS);
+        if !(0..=MAX_EXPIRING_WITHIN_DAYS).contains(&within_days) {
+            return error_response(
+                Status::BadRequest,
+                &format!(
+                    "within_days must be between 0 and {}",
+                    MAX_EXPIRING_WITHIN_DAYS
+                ),
+            );
+        }
+        let until = Utc::now().naive_utc().date() + Duration::days(within_days);
+        success_response(json!(PantryItem::get_expiring(
+            &req.state.connection,
+            req.token.user_id,
+            until
+        )))
+    })
+}
+
+#[post("/pantry", data = "<new_item>")]
+pub fn post_pantry_item(
+    request: Result<UserRequest, JsonResponse>,
+    new_item: Option<Json<PantryItemData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_item {
+            Some(item_data) => {
+                let quantity = Quantity::new(item_data.quantity, item_data.unit);
+                if let Err(err) = validate_item_quantity(
+                    &req.state.connection,
+                    ItemVisibility::User(req.token.user_id),
+                    item_data.item_id,
+                    &quantity,
+                ) {
+                    return err;
+                }
+                let result = PantryItem::insert_pantry_item(
+                    &req.state.connection,
+                    &NewPantryItem {
+                        user_id: req.token.user_id,
+                        item_id: item_data.item_id,
+                        quantity: item_data.quantity,
+                        unit: item_data.unit,
+                        location: item_data.location,
+                        expiry_date: item_data.expiry_date,
+                    },
+                );
+                if result {
+                    let item = PantryItem::get_last_inserted_pantry_item(&req.state.connection);
+                    match item {
+                        Ok(inserted_item) => success_response(json!(inserted_item)),
+                        Err(_) => error_response(
+                            Status::InternalServerError,
+                            "Failed to insert pantry item",
+                        ),
+                    }
+                } else {
+                    error_response(Status::InternalServerError, "Failed to insert pantry item")
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse pantry item data"),
+        }
+    })
+}
+
+#[post("/pantry/consume", data = "<consumption>")]
+pub fn post_consume(
+    request: Result<UserRequest, JsonResponse>,
+    consumption: Option<Json<ItemQuantityData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &consumption {
+            Some(consume_data) => {
+                let quantity = Quantity::new(consume_data.quantity, consume_data.unit);
+                if let Err(err) = validate_item_quantity(
+                    &req.state.connection,
+                    ItemVisibility::User(req.token.user_id),
+                    consume_data.item_id,
+                    &quantity,
+                ) {
+                    return err;
+                }
+                let result = PantryItem::consume(
+                    &req.state.connection,
+                    req.token.user_id,
+                    consume_data.item_id,
+                    quantity,
+                );
+                match result {
+                    Ok(remaining) => success_response(json!(remaining)),
+                    Err(ConsumeError::NotEnoughStock) => {
+                        error_response(Status::BadRequest, "Not enough in stock")
+                    }
+                    Err(ConsumeError::Database(_)) => {
+                        error_response(Status::InternalServerError, "Failed to update pantry")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse consumption data"),
+        }
+    })
+}
+
+#[delete("/pantry/<pantry_item_id>")]
+pub fn delete_pantry_item(
+    request: Result<UserRequest, JsonResponse>,
+    pantry_item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let potential_item =
+            PantryItem::get_pantry_item_by_id(&req.state.connection, pantry_item_id);
+        match potential_item {
+            Ok(item) if item.user_id == req.token.user_id => {
+                let success = PantryItem::delete_pantry_item(&req.state.connection, pantry_item_id);
+                if success {
+                    success_response(json!(item))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete pantry item")
+                }
+            }
+            _ => error_response(Status::NotFound, "Pantry item not found"),
+        }
+    })
+}
+
+#[post("/pantry/thresholds", data = "<threshold>")]
+pub fn post_threshold(
+    request: Result<UserRequest, JsonResponse>,
+    threshold: Option<Json<ItemQuantityData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &threshold {
+            Some(threshold_data) => {
+                let quantity = Quantity::new(threshold_data.quantity, threshold_data.unit);
+                if let Err(err) = validate_item_quantity(
+                    &req.state.connection,
+                    ItemVisibility::User(req.token.user_id),
+                    threshold_data.item_id,
+                    &quantity,
+                ) {
+                    return err;
+                }
+                let threshold = PantryThreshold {
+                    user_id: req.token.user_id,
+                    item_id: threshold_data.item_id,
+                    quantity: threshold_data.quantity,
+                    unit: threshold_data.unit,
+                };
+                if PantryThreshold::upsert_threshold(&req.state.connection, &threshold) {
+                    success_response(json!(threshold))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to save threshold")
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse threshold data"),
+        }
+    })
+}
+
+#[delete("/pantry/thresholds/<item_id>")]
+pub fn delete_threshold(request: Result<UserRequest, JsonResponse>, item_id: i32) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let success =
+            PantryThreshold::delete_threshold(&req.state.connection, req.token.user_id, item_id);
+        if success {
+            success_response(json!({ "item_id": item_id }))
+        } else {
+            error_response(Status::InternalServerError, "Failed to delete threshold")
+        }
+    })
+}
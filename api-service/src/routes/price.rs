@@ -0,0 +1,189 @@
+use chrono::{NaiveDateTime, Utc};
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{OptionalUserRequest, UserRequest},
+    models::item::{ItemVisibility, ShoppingItem},
+    models::price::{is_valid_currency, ItemPrice, NewItemPrice},
+    models::store::Store,
+    models::unit::{Quantity, Unit},
+    models::variant::ItemVariant,
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct PriceObservation {
+    pub store_id: i32,
+    pub price_cents: i32,
+    pub currency: String,
+    /// May be left out when `variant_id` is given, to use the variant's package size.
+    pub quantity: Option<f64>,
+    pub unit: Option<Unit>,
+    pub observed_at: Option<NaiveDateTime>,
+    pub variant_id: Option<i32>,
+}
+
+#[get("/items/<item_id>/prices")]
+pub fn get_latest_prices(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        success_response(json!(ItemPrice::get_latest_prices(
+            &req.state.connection,
+            item_id
+        )))
+    })
+}
+
+#[get("/items/<item_id>/prices/cheapest?<currency>")]
+pub fn get_cheapest_price(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+    currency: Option<String>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        let currency = match currency {
+            Some(currency) if is_valid_currency(&currency) => currency,
+            Some(_) => return error_response(Status::BadRequest, "Invalid currency code"),
+            None => return error_response(Status::BadRequest, "A currency is required"),
+        };
+        let cheapest = ItemPrice::get_cheapest_price(&req.state.connection, item_id, &currency);
+        match cheapest {
+            Some(price) => success_response(json!({
+                "unit_price": price.unit_price(),
+                "per": Unit::base_unit(price.unit.unit_type()).symbol(),
+                "price": price,
+            })),
+            None => error_response(Status::NotFound, "No comparable prices found"),
+        }
+    })
+}
+
+#[get("/items/<item_id>/prices/history?<store_id>&<variant_id>")]
+pub fn get_price_history(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+    store_id: Option<i32>,
+    variant_id: Option<i32>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        success_response(json!(ItemPrice::get_price_history(
+            &req.state.connection,
+            item_id,
+            store_id,
+            variant_id
+        )))
+    })
+}
+
+#[post("/items/<item_id>/prices", data = "<observation>")]
+pub fn post_new_price(
+    request: Result<UserRequest, JsonResponse>,
+    item_id: i32,
+    observation: Option<Json<PriceObservation>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &observation {
+            Some(price_data) => {
+                let item = match ShoppingItem::get_item_by_id(
+                    &req.state.connection,
+                    item_id,
+                    ItemVisibility::User(req.token.user_id),
+                ) {
+                    Ok(item) => item,
+                    Err(_) => return error_response(Status::NotFound, "Item not found"),
+                };
+                if Store::get_store_by_id(&req.state.connection, price_data.store_id).is_err() {
+                    return error_response(Status::NotFound, "Store not found");
+                }
+                let variant = match price_data.variant_id {
+                    Some(variant_id) => {
+                        match ItemVariant::get_item_variant(
+                            &req.state.connection,
+                            item_id,
+                            variant_id,
+                        ) {
+                            Ok(variant) => Some(variant),
+                            Err(_) => return error_response(Status::NotFound, "Variant not found"),
+                        }
+                    }
+                    None => None,
+                };
+                let quantity = match (&variant, price_data.quantity, price_data.unit) {
+                    (Some(variant), None, None) => variant.package_size(),
+                    (Some(variant), Some(amount), Some(unit)) => {
+                        let quantity = Quantity::new(amount, unit);
+                        if !variant.is_package_size(&quantity) {
+                            return error_response(
+                                Status::BadRequest,
+                                "Quantity does not match the variant's package size",
+                            );
+                        }
+                        quantity
+                    }
+                    (None, Some(amount), Some(unit)) => Quantity::new(amount, unit),
+                    _ => {
+                        return error_response(
+                            Status::BadRequest,
+                            "Quantity and unit are required unless a variant is given",
+                        )
+                    }
+                };
+                if price_data.price_cents < 0 || quantity.amount <= 0.0 {
+                    return error_response(Status::BadRequest, "Invalid price or quantity");
+                }
+                if !is_valid_currency(&price_data.currency) {
+                    return error_response(Status::BadRequest, "Invalid currency code");
+                }
+                if let Err(err) = item.check_quantity(&quantity) {
+                    return error_response(Status::BadRequest, &err.to_string());
+                }
+                let result = ItemPrice::insert_price(
+                    &req.state.connection,
+                    &NewItemPrice {
+                        item_id,
+                        store_id: price_data.store_id,
+                        reporter_id: req.token.user_id,
+                        price_cents: price_data.price_cents,
+                        currency: price_data.currency.clone(),
+                        quantity: quantity.amount,
+                        unit: quantity.unit,
+                        observed_at: price_data
+                            .observed_at
+                            .unwrap_or_else(|| Utc::now().naive_utc()),
+                        variant_id: price_data.variant_id,
+                    },
+                );
+                if result {
+                    let price = ItemPrice::get_last_inserted_price(&req.state.connection);
+                    match price {
+                        Ok(inserted_price) => success_response(json!(inserted_price)),
+                        Err(_) => {
+                            error_response(Status::InternalServerError, "Failed to insert price")
+                        }
+                    }
+                } else {
+                    error_response(Status::InternalServerError, "Failed to insert price")
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse price data"),
+        }
+    })
+}
@@ -0,0 +1,231 @@
+use diesel::pg::PgConnection;
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{AdminRequest, UserRequest},
+    models::item::{ItemVisibility, NewShoppingItem, ShoppingItem, UnitType},
+    models::proposal::{ItemProposal, NewItemProposal, ProposalStatus},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+const MAX_REASON_LENGTH: usize = 512;
+
+#[derive(Deserialize)]
+pub struct ProposalData {
+    pub item_id: Option<i32>,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub default_unit_type: UnitType,
+}
+
+#[derive(Deserialize)]
+pub struct RejectionData {
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProposalMergeData {
+    pub item_id: i32,
+}
+
+fn get_pending_proposal(
+    req: &AdminRequest,
+    proposal_id: i32,
+) -> Result<ItemProposal, JsonResponse> {
+    match ItemProposal::get_proposal_by_id(&req.state.connection, proposal_id) {
+        Ok(proposal) if proposal.status == ProposalStatus::Pending => Ok(proposal),
+        Ok(_) => Err(error_response(
+            Status::Conflict,
+            "Proposal has already been reviewed",
+        )),
+        Err(_) => Err(error_response(Status::NotFound, "Proposal not found")),
+    }
+}
+
+/// Loads the shared item an edit proposal targets, checking the edit does not change
+/// the unit type it is measured in, which the item's prices, recipes and stock depend on.
+fn get_edited_item(
+    conn: &PgConnection,
+    item_id: i32,
+    default_unit_type: UnitType,
+) -> Result<ShoppingItem, JsonResponse> {
+    match ShoppingItem::get_item_by_id(conn, item_id, ItemVisibility::Global) {
+        Ok(item) if item.default_unit_type == default_unit_type => Ok(item),
+        Ok(_) => Err(error_response(
+            Status::BadRequest,
+            "Cannot change the unit type of an existing item",
+        )),
+        Err(_) => Err(error_response(Status::NotFound, "Item not found")),
+    }
+}
+
+#[get("/items/proposals")]
+pub fn get_my_proposals(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(ItemProposal::get_user_proposals(
+            &req.state.connection,
+            req.token.user_id
+        )))
+    })
+}
+
+#[get("/items/proposals/pending")]
+pub fn get_pending_proposals(request: Result<AdminRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(ItemProposal::get_pending_proposals(
+            &req.state.connection
+        )))
+    })
+}
+
+/// Proposes a new item, or changes to an existing one when `item_id` is given, for an
+/// admin to review.
+#[post("/items/proposals", data = "<new_proposal>")]
+pub fn post_new_proposal(
+    request: Result<UserRequest, JsonResponse>,
+    new_proposal: Option<Json<ProposalData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_proposal {
+            Some(proposal_data) => {
+                let mut item = NewShoppingItem {
+                    name: String::from(proposal_data.name.trim()),
+                    description: proposal_data.description.clone(),
+                    image_url: proposal_data.image_url.clone(),
+                    default_unit_type: proposal_data.default_unit_type,
+                    owner_id: None,
+                };
+                if let Some(item_id) = proposal_data.item_id {
+                    let current = match get_edited_item(
+                        &req.state.connection,
+                        item_id,
+                        item.default_unit_type,
+                    ) {
+                        Ok(current) => current,
+                        Err(err) => return err,
+                    };
+                    // An edit that leaves out the description or image keeps the item's
+                    // current one rather than clearing it when approved.
+                    if item.description.is_none() {
+                        item.description = current.description;
+                    }
+                    if item.image_url.is_none() {
+                        item.image_url = current.image_url;
+                    }
+                }
+                if let Err(err) = item.validate() {
+                    return error_response(Status::BadRequest, &err);
+                }
+                let result = ItemProposal::insert_proposal(
+                    &req.state.connection,
+                    &NewItemProposal {
+                        proposed_by: req.token.user_id,
+                        item_id: proposal_data.item_id,
+                        name: item.name,
+                        description: item.description,
+                        image_url: item.image_url,
+                        default_unit_type: item.default_unit_type,
+                    },
+                );
+                match result {
+                    Ok(proposal) => success_response(json!(proposal)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to insert proposal")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse proposal data"),
+        }
+    })
+}
+
+#[post("/items/proposals/<proposal_id>/approve")]
+pub fn post_approve_proposal(
+    request: Result<AdminRequest, JsonResponse>,
+    proposal_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let proposal = match get_pending_proposal(&req, proposal_id) {
+            Ok(proposal) => proposal,
+            Err(err) => return err,
+        };
+        if let Some(item_id) = proposal.item_id {
+            if let Err(err) =
+                get_edited_item(&req.state.connection, item_id, proposal.default_unit_type)
+            {
+                return err;
+            }
+        }
+        match proposal.approve(&req.state.connection) {
+            Ok(proposal) => success_response(json!(proposal)),
+            Err(_) => error_response(Status::InternalServerError, "Failed to approve proposal"),
+        }
+    })
+}
+
+#[post("/items/proposals/<proposal_id>/reject", data = "<rejection>")]
+pub fn post_reject_proposal(
+    request: Result<AdminRequest, JsonResponse>,
+    proposal_id: i32,
+    rejection: Option<Json<RejectionData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let reason = match &rejection {
+            Some(data) => data.reason.trim(),
+            None => return error_response(Status::BadRequest, "Failed to parse rejection data"),
+        };
+        if reason.is_empty() || reason.len() > MAX_REASON_LENGTH {
+            return error_response(Status::BadRequest, "Invalid rejection reason");
+        }
+        let proposal = match get_pending_proposal(&req, proposal_id) {
+            Ok(proposal) => proposal,
+            Err(err) => return err,
+        };
+        match proposal.reject(&req.state.connection, reason) {
+            Ok(proposal) => success_response(json!(proposal)),
+            Err(_) => error_response(Status::InternalServerError, "Failed to reject proposal"),
+        }
+    })
+}
+
+/// Resolves a new-item proposal by pointing it at an existing item instead.
+#[post("/items/proposals/<proposal_id>/merge", data = "<merge_data>")]
+pub fn post_merge_proposal(
+    request: Result<AdminRequest, JsonResponse>,
+    proposal_id: i32,
+    merge_data: Option<Json<ProposalMergeData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let item_id = match &merge_data {
+            Some(data) => data.item_id,
+            None => return error_response(Status::BadRequest, "Failed to parse merge data"),
+        };
+        let proposal = match get_pending_proposal(&req, proposal_id) {
+            Ok(proposal) => proposal,
+            Err(err) => return err,
+        };
+        if proposal.item_id.is_some() {
+            return error_response(
+                Status::BadRequest,
+                "Only proposals for new items can be merged",
+            );
+        }
+        let item = match ShoppingItem::get_item_by_id(
+            &req.state.connection,
+            item_id,
+            ItemVisibility::Global,
+        ) {
+            Ok(item) => item,
+            Err(_) => return error_response(Status::NotFound, "Item not found"),
+        };
+        match proposal.merge_into(&req.state.connection, &item) {
+            Ok(proposal) => success_response(json!(proposal)),
+            Err(_) => error_response(Status::InternalServerError, "Failed to merge proposal"),
+        }
+    })
+}
@@ -0,0 +1,239 @@
+use diesel::pg::PgConnection;
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::UserRequest,
+    models::item::{ItemQuantityError, ItemVisibility, ShoppingItem},
+    models::recipe::{NewRecipe, Recipe},
+    models::restriction::UserDietaryRestriction,
+    models::unit::{Quantity, Unit},
+    responses::{error_response, success_response, success_response_with_warnings, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct IngredientData {
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Deserialize)]
+pub struct RecipeData {
+    pub name: String,
+    pub description: Option<String>,
+    pub servings: i32,
+    #[serde(default)]
+    pub steps: Vec<String>,
+    pub ingredients: Vec<IngredientData>,
+}
+
+fn validate_ingredients(
+    conn: &PgConnection,
+    visibility: ItemVisibility,
+    ingredients: &[IngredientData],
+) -> Result<Vec<(i32, Quantity)>, JsonResponse> {
+    ingredients
+        .iter()
+        .map(|ingredient| {
+            let quantity = Quantity::new(ingredient.quantity, ingredient.unit);
+            ShoppingItem::validate_quantity(conn, ingredient.item_id, &quantity, visibility)
+                .map_err(|err| match err {
+                    ItemQuantityError::NotPositive => {
+                        error_response(Status::BadRequest, "Ingredient quantities must be positive")
+                    }
+                    ItemQuantityError::ItemNotFound => {
+                        error_response(Status::NotFound, "Ingredient item not found")
+                    }
+                    ItemQuantityError::WrongUnit(err) => {
+                        error_response(Status::BadRequest, &err.to_string())
+                    }
+                })?;
+            Ok((ingredient.item_id, quantity))
+        })
+        .collect()
+}
+
+fn to_new_recipe(recipe_data: &RecipeData, created_by: i32) -> NewRecipe {
+    NewRecipe {
+        name: recipe_data.name.clone(),
+        description: recipe_data.description.clone(),
+        servings: recipe_data.servings,
+        steps: recipe_data.steps.clone(),
+        created_by,
+    }
+}
+
+fn can_modify(req: &UserRequest, recipe: &Recipe) -> bool {
+    req.token.is_admin || recipe.created_by == req.token.user_id
+}
+
+#[get("/recipes")]
+pub fn get_all_recipes(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(Recipe::get_all_recipes(&req.state.connection)))
+    })
+}
+
+#[get("/recipes/<recipe_id>")]
+pub fn get_recipe(request: Result<UserRequest, JsonResponse>, recipe_id: i32) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match Recipe::get_recipe_details(&req.state.connection, recipe_id) {
+            Ok(details) => {
+                let item_ids: Vec<i32> = details.ingredients.iter().map(|i| i.item_id).collect();
+                let conflicts = UserDietaryRestriction::find_conflicts(
+                    &req.state.connection,
+                    req.token.user_id,
+                    &item_ids,
+                );
+                success_response_with_warnings(json!(details), json!(conflicts))
+            }
+            Err(_) => error_response(Status::NotFound, "Recipe not found"),
+        }
+    })
+}
+
+#[get("/recipes/<recipe_id>/ingredients?<servings>")]
+pub fn get_recipe_ingredients(
+    request: Result<UserRequest, JsonResponse>,
+    recipe_id: i32,
+    servings: Option<i32>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match Recipe::get_recipe_details(&req.state.connection, recipe_id) {
+            Ok(details) => {
+                let servings = servings.unwrap_or(details.recipe.servings);
+                if servings <= 0 {
+                    return error_response(Status::BadRequest, "Servings must be positive");
+                }
+                let scaled = details.scaled_ingredients(servings);
+                let item_ids: Vec<i32> = scaled.iter().map(|(item_id, _)| *item_id).collect();
+                let conflicts = UserDietaryRestriction::find_conflicts(
+                    &req.state.connection,
+                    req.token.user_id,
+                    &item_ids,
+                );
+                let ingredients: Vec<_> = scaled
+                    .into_iter()
+                    .map(|(item_id, quantity)| json!({ "item_id": item_id, "quantity": quantity }))
+                    .collect();
+                success_response_with_warnings(
+                    json!({
+                        "servings": servings,
+                        "ingredients": ingredients,
+                    }),
+                    json!(conflicts),
+                )
+            }
+            Err(_) => error_response(Status::NotFound, "Recipe not found"),
+        }
+    })
+}
+
+#[post("/recipes", data = "<new_recipe>")]
+pub fn post_new_recipe(
+    request: Result<UserRequest, JsonResponse>,
+    new_recipe: Option<Json<RecipeData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_recipe {
+            Some(recipe_data) => {
+                if recipe_data.servings <= 0 {
+                    return error_response(Status::BadRequest, "Servings must be positive");
+                }
+                let ingredients = match validate_ingredients(
+                    &req.state.connection,
+                    ItemVisibility::Global,
+                    &recipe_data.ingredients,
+                ) {
+                    Ok(ingredients) => ingredients,
+                    Err(err) => return err,
+                };
+                let result = Recipe::insert_recipe(
+                    &req.state.connection,
+                    &to_new_recipe(recipe_data, req.token.user_id),
+                    &ingredients,
+                );
+                match result {
+                    Ok(details) => success_response(json!(details)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to insert recipe")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse recipe data"),
+        }
+    })
+}
+
+#[patch("/recipes/<recipe_id>", data = "<recipe_update>")]
+pub fn patch_recipe(
+    request: Result<UserRequest, JsonResponse>,
+    recipe_id: i32,
+    recipe_update: Option<Json<RecipeData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let existing = match Recipe::get_recipe_by_id(&req.state.connection, recipe_id) {
+            Ok(recipe) => recipe,
+            Err(_) => return error_response(Status::NotFound, "Recipe not found"),
+        };
+        if !can_modify(&req, &existing) {
+            return error_response(Status::Forbidden, "Cannot modify another user's recipe");
+        }
+        match &recipe_update {
+            Some(recipe_data) => {
+                if recipe_data.servings <= 0 {
+                    return error_response(Status::BadRequest, "Servings must be positive");
+                }
+                let ingredients = match validate_ingredients(
+                    &req.state.connection,
+                    ItemVisibility::Global,
+                    &recipe_data.ingredients,
+                ) {
+                    Ok(ingredients) => ingredients,
+                    Err(err) => return err,
+                };
+                let result = Recipe::update_recipe(
+                    &req.state.connection,
+                    recipe_id,
+                    &to_new_recipe(recipe_data, existing.created_by),
+                    &ingredients,
+                );
+                match result {
+                    Ok(details) => success_response(json!(details)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to update recipe")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse recipe data"),
+        }
+    })
+}
+
+#[delete("/recipes/<recipe_id>")]
+pub fn delete_recipe(request: Result<UserRequest, JsonResponse>, recipe_id: i32) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let potential_recipe = Recipe::get_recipe_by_id(&req.state.connection, recipe_id);
+        match potential_recipe {
+            Ok(recipe) => {
+                if !can_modify(&req, &recipe) {
+                    return error_response(
+                        Status::Forbidden,
+                        "Cannot delete another user's recipe",
+                    );
+                }
+                let success = Recipe::delete_recipe(&req.state.connection, recipe_id);
+                if success {
+                    success_response(json!(recipe))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete recipe")
+                }
+            }
+            Err(_) => error_response(Status::NotFound, "Recipe not found"),
+        }
+    })
+}
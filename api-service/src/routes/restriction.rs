@@ -0,0 +1,52 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::UserRequest,
+    models::restriction::{DietaryRestriction, UserDietaryRestriction},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct RestrictionsData {
+    pub restrictions: Vec<DietaryRestriction>,
+}
+
+#[get("/users/me/restrictions")]
+pub fn get_my_restrictions(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(UserDietaryRestriction::get_restrictions(
+            &req.state.connection,
+            req.token.user_id
+        )))
+    })
+}
+
+/// Replaces the caller's dietary restrictions.
+#[post("/users/me/restrictions", data = "<restrictions>")]
+pub fn post_my_restrictions(
+    request: Result<UserRequest, JsonResponse>,
+    restrictions: Option<Json<RestrictionsData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &restrictions {
+            Some(data) => {
+                let result = UserDietaryRestriction::set_restrictions(
+                    &req.state.connection,
+                    req.token.user_id,
+                    &data.restrictions,
+                );
+                match result {
+                    Ok(restrictions) => success_response(json!(restrictions)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to save restrictions")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse restrictions"),
+        }
+    })
+}
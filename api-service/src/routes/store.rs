@@ -0,0 +1,94 @@
+use rocket::http::Status;
+
+use crate::{
+    auth::{AdminRequest, PublicRequest},
+    models::store::{is_valid_location, NewStore, Store},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+const DEFAULT_NEARBY_RADIUS: f64 = 200.0;
+const MAX_NEARBY_RADIUS: f64 = 50_000.0;
+
+#[get("/stores")]
+pub fn get_all_stores(request: Result<PublicRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(Store::get_all_stores(&req.state.connection)))
+    })
+}
+
+#[get("/stores/nearby?<lat>&<lon>&<radius>")]
+pub fn get_nearby_stores(
+    request: Result<PublicRequest, JsonResponse>,
+    lat: f64,
+    lon: f64,
+    radius: Option<f64>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let radius = radius.unwrap_or(DEFAULT_NEARBY_RADIUS);
+        if !is_valid_location(lat, lon) {
+            error_response(Status::BadRequest, "Invalid location")
+        } else if radius <= 0.0 || radius > MAX_NEARBY_RADIUS {
+            error_response(
+                Status::BadRequest,
+                &format!("Radius must be between 0 and {} metres", MAX_NEARBY_RADIUS),
+            )
+        } else {
+            success_response(json!(Store::get_nearby_stores(
+                &req.state.connection,
+                lat,
+                lon,
+                radius
+            )))
+        }
+    })
+}
+
+#[post("/stores", data = "<new_store>")]
+pub fn post_new_store(
+    request: Result<AdminRequest, JsonResponse>,
+    new_store: Option<Json<NewStore>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_store {
+            Some(store_data) => {
+                if !is_valid_location(store_data.latitude, store_data.longitude) {
+                    return error_response(Status::BadRequest, "Invalid store location");
+                }
+                let result = Store::insert_store(&req.state.connection, store_data);
+                if result {
+                    let store = Store::get_last_inserted_store(&req.state.connection);
+                    match store {
+                        Ok(inserted_store) => success_response(json!(inserted_store)),
+                        Err(_) => {
+                            error_response(Status::InternalServerError, "Failed to insert store")
+                        }
+                    }
+                } else {
+                    error_response(Status::InternalServerError, "Failed to insert store")
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse new store data"),
+        }
+    })
+}
+
+#[delete("/stores/<store_id>")]
+pub fn delete_store(request: Result<AdminRequest, JsonResponse>, store_id: i32) -> JsonResponse {
+    handle_request(request, |req: AdminRequest| -> JsonResponse {
+        let potential_store = Store::get_store_by_id(&req.state.connection, store_id);
+        match potential_store {
+            Ok(store) => {
+                let success = Store::delete_store(&req.state.connection, store_id);
+                if success {
+                    success_response(json!(store))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete store")
+                }
+            }
+            Err(_) => error_response(Status::NotFound, "Store not found"),
+        }
+    })
+}
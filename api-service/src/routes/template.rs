@@ -0,0 +1,183 @@
+use diesel::pg::PgConnection;
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::UserRequest,
+    models::item::{ItemQuantityError, ItemVisibility, ShoppingItem},
+    models::restriction::UserDietaryRestriction,
+    models::template::{ListTemplate, NewListTemplate},
+    models::unit::{Quantity, Unit},
+    responses::{error_response, success_response, success_response_with_warnings, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct TemplateItemData {
+    pub item_id: i32,
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+#[derive(Deserialize)]
+pub struct TemplateData {
+    pub name: String,
+    pub items: Vec<TemplateItemData>,
+}
+
+fn validate_template_items(
+    conn: &PgConnection,
+    visibility: ItemVisibility,
+    items: &[TemplateItemData],
+) -> Result<Vec<(i32, Quantity)>, JsonResponse> {
+    items
+        .iter()
+        .map(|item| {
+            let quantity = Quantity::new(item.quantity, item.unit);
+            ShoppingItem::validate_quantity(conn, item.item_id, &quantity, visibility).map_err(
+                |err| match err {
+                    ItemQuantityError::NotPositive => {
+                        error_response(Status::BadRequest, "Quantities must be positive")
+                    }
+                    ItemQuantityError::ItemNotFound => {
+                        error_response(Status::NotFound, "Item not found")
+                    }
+                    ItemQuantityError::WrongUnit(err) => {
+                        error_response(Status::BadRequest, &err.to_string())
+                    }
+                },
+            )?;
+            Ok((item.item_id, quantity))
+        })
+        .collect()
+}
+
+#[get("/templates")]
+pub fn get_templates(request: Result<UserRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        success_response(json!(ListTemplate::get_templates_for_user(
+            &req.state.connection,
+            req.token.user_id
+        )))
+    })
+}
+
+#[get("/templates/<template_id>")]
+pub fn get_template(request: Result<UserRequest, JsonResponse>, template_id: i32) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match ListTemplate::get_template_details(&req.state.connection, template_id) {
+            Ok(details) if details.template.created_by == req.token.user_id => {
+                let item_ids: Vec<i32> = details.items.iter().map(|i| i.item_id).collect();
+                let conflicts = UserDietaryRestriction::find_conflicts(
+                    &req.state.connection,
+                    req.token.user_id,
+                    &item_ids,
+                );
+                success_response_with_warnings(json!(details), json!(conflicts))
+            }
+            _ => error_response(Status::NotFound, "Template not found"),
+        }
+    })
+}
+
+#[post("/templates", data = "<new_template>")]
+pub fn post_new_template(
+    request: Result<UserRequest, JsonResponse>,
+    new_template: Option<Json<TemplateData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &new_template {
+            Some(template_data) => {
+                let items = match validate_template_items(
+                    &req.state.connection,
+                    ItemVisibility::User(req.token.user_id),
+                    &template_data.items,
+                ) {
+                    Ok(items) => items,
+                    Err(err) => return err,
+                };
+                let result = ListTemplate::insert_template(
+                    &req.state.connection,
+                    &NewListTemplate {
+                        name: template_data.name.clone(),
+                        created_by: req.token.user_id,
+                    },
+                    &items,
+                );
+                match result {
+                    Ok(details) => success_response(json!(details)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to insert template")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse template data"),
+        }
+    })
+}
+
+#[patch("/templates/<template_id>", data = "<template_update>")]
+pub fn patch_template(
+    request: Result<UserRequest, JsonResponse>,
+    template_id: i32,
+    template_update: Option<Json<TemplateData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match ListTemplate::get_template_by_id(&req.state.connection, template_id) {
+            Ok(template) if template.created_by == req.token.user_id => {}
+            _ => return error_response(Status::NotFound, "Template not found"),
+        }
+        match &template_update {
+            Some(template_data) => {
+                let items = match validate_template_items(
+                    &req.state.connection,
+                    ItemVisibility::User(req.token.user_id),
+                    &template_data.items,
+                ) {
+                    Ok(items) => items,
+                    Err(err) => return err,
+                };
+                let result = ListTemplate::update_template(
+                    &req.state.connection,
+                    template_id,
+                    &NewListTemplate {
+                        name: template_data.name.clone(),
+                        created_by: req.token.user_id,
+                    },
+                    &items,
+                );
+                match result {
+                    Ok(details) => success_response(json!(details)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to update template")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse template data"),
+        }
+    })
+}
+
+#[delete("/templates/<template_id>")]
+pub fn delete_template(
+    request: Result<UserRequest, JsonResponse>,
+    template_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req: UserRequest| -> JsonResponse {
+        let potential_template =
+            ListTemplate::get_template_by_id(&req.state.connection, template_id);
+        match potential_template {
+            Ok(template) if template.created_by == req.token.user_id => {
+                let success = ListTemplate::delete_template(&req.state.connection, template_id);
+                if success {
+                    success_response(json!(template))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete template")
+                }
+            }
+            _ => error_response(Status::NotFound, "Template not found"),
+        }
+    })
+}
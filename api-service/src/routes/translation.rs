@@ -0,0 +1,107 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{AdminRequest, OptionalUserRequest},
+    locale::normalize_locale,
+    models::item::{ItemVisibility, ShoppingItem, MAX_NAME_LENGTH},
+    models::translation::ItemTranslation,
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct TranslationData {
+    pub locale: String,
+    pub name: String,
+}
+
+#[get("/items/<item_id>/translations")]
+pub fn get_item_translations(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        success_response(json!(ItemTranslation::get_item_translations(
+            &req.state.connection,
+            item_id
+        )))
+    })
+}
+
+/// Sets the item's name for a locale, replacing any existing translation.
+#[post("/items/<item_id>/translations", data = "<translation>")]
+pub fn post_item_translation(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    translation: Option<Json<TranslationData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        match &translation {
+            Some(translation_data) => {
+                let locale = match normalize_locale(&translation_data.locale) {
+                    Some(locale) => locale,
+                    None => return error_response(Status::BadRequest, "Invalid locale"),
+                };
+                let name = translation_data.name.trim();
+                if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+                    return error_response(Status::BadRequest, "Invalid name");
+                }
+                if ShoppingItem::get_item_by_id(&req.state.connection, item_id, ItemVisibility::All)
+                    .is_err()
+                {
+                    return error_response(Status::NotFound, "Item not found");
+                }
+                let result = ItemTranslation::upsert_translation(
+                    &req.state.connection,
+                    &ItemTranslation {
+                        item_id,
+                        locale,
+                        name: String::from(name),
+                    },
+                );
+                match result {
+                    Ok(translation) => success_response(json!(translation)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to save translation")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse translation data"),
+        }
+    })
+}
+
+#[delete("/items/<item_id>/translations/<locale>")]
+pub fn delete_item_translation(
+    request: Result<AdminRequest, JsonResponse>,
+    item_id: i32,
+    locale: String,
+) -> JsonResponse {
+    handle_request(request, |req: AdminRequest| -> JsonResponse {
+        let translation = normalize_locale(&locale).and_then(|locale| {
+            ItemTranslation::get_translation(&req.state.connection, item_id, &locale).ok()
+        });
+        match translation {
+            Some(translation) => {
+                let success = ItemTranslation::delete_translation(
+                    &req.state.connection,
+                    item_id,
+                    &translation.locale,
+                );
+                if success {
+                    success_response(json!(translation))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete translation")
+                }
+            }
+            None => error_response(Status::NotFound, "Translation not found"),
+        }
+    })
+}
@@ -0,0 +1,21 @@
+use crate::auth::PublicRequest;
+use crate::models::unit::Unit;
+use crate::responses::{success_response, JsonResponse};
+use crate::utils::handle_request;
+
+#[get("/units")]
+pub fn get_units(request: Result<PublicRequest, JsonResponse>) -> JsonResponse {
+    handle_request(request, |_| -> JsonResponse {
+        let units: Vec<_> = Unit::ALL
+            .iter()
+            .map(|unit| {
+                json!({
+                    "unit": unit,
+                    "symbol": unit.symbol(),
+                    "unit_type": unit.unit_type(),
+                })
+            })
+            .collect();
+        success_response(json!(units))
+    })
+}
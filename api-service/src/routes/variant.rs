@@ -0,0 +1,110 @@
+use rocket::http::Status;
+use serde_derive::Deserialize;
+
+use crate::{
+    auth::{OptionalUserRequest, UserRequest},
+    models::item::{ItemVisibility, ShoppingItem},
+    models::unit::Unit,
+    models::variant::{ItemVariant, NewItemVariant},
+    responses::{error_response, success_response, JsonResponse},
+    utils::handle_request,
+};
+
+use rocket_contrib::json::Json;
+
+#[derive(Deserialize)]
+pub struct NewVariantData {
+    pub brand: String,
+    pub package_quantity: f64,
+    pub package_unit: Unit,
+    pub image_url: Option<String>,
+}
+
+/// Finds an item the user may add variants to or remove them from: any shared item for
+/// admins, and the user's own private items.
+fn get_editable_item(req: &UserRequest, item_id: i32) -> Result<ShoppingItem, JsonResponse> {
+    let visibility = ItemVisibility::User(req.token.user_id);
+    match ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility) {
+        Ok(item) if req.token.is_admin || item.owner_id == Some(req.token.user_id) => Ok(item),
+        Ok(_) => Err(error_response(Status::Forbidden, "Admin access required")),
+        Err(_) => Err(error_response(Status::NotFound, "Item not found")),
+    }
+}
+
+#[get("/items/<item_id>/variants")]
+pub fn get_item_variants(
+    request: Result<OptionalUserRequest, JsonResponse>,
+    item_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let visibility = ItemVisibility::for_user(req.user_id());
+        if ShoppingItem::get_item_by_id(&req.state.connection, item_id, visibility).is_err() {
+            return error_response(Status::NotFound, "Item not found");
+        }
+        success_response(json!(ItemVariant::get_item_variants(
+            &req.state.connection,
+            item_id
+        )))
+    })
+}
+
+#[post("/items/<item_id>/variants", data = "<new_variant>")]
+pub fn post_item_variant(
+    request: Result<UserRequest, JsonResponse>,
+    item_id: i32,
+    new_variant: Option<Json<NewVariantData>>,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        let item = match get_editable_item(&req, item_id) {
+            Ok(item) => item,
+            Err(response) => return response,
+        };
+        match &new_variant {
+            Some(variant_data) => {
+                let variant = NewItemVariant {
+                    item_id,
+                    brand: String::from(variant_data.brand.trim()),
+                    package_quantity: variant_data.package_quantity,
+                    package_unit: variant_data.package_unit,
+                    image_url: variant_data.image_url.clone(),
+                };
+                if let Err(msg) = variant.validate() {
+                    return error_response(Status::BadRequest, &msg);
+                }
+                if let Err(err) = item.check_quantity(&variant.package_size()) {
+                    return error_response(Status::BadRequest, &err.to_string());
+                }
+                match ItemVariant::insert_variant(&req.state.connection, &variant) {
+                    Ok(variant) => success_response(json!(variant)),
+                    Err(_) => {
+                        error_response(Status::InternalServerError, "Failed to insert variant")
+                    }
+                }
+            }
+            None => error_response(Status::BadRequest, "Failed to parse variant data"),
+        }
+    })
+}
+
+#[delete("/items/<item_id>/variants/<variant_id>")]
+pub fn delete_item_variant(
+    request: Result<UserRequest, JsonResponse>,
+    item_id: i32,
+    variant_id: i32,
+) -> JsonResponse {
+    handle_request(request, |req| -> JsonResponse {
+        if let Err(response) = get_editable_item(&req, item_id) {
+            return response;
+        }
+        match ItemVariant::get_item_variant(&req.state.connection, item_id, variant_id) {
+            Ok(variant) => {
+                if ItemVariant::delete_variant(&req.state.connection, variant.id) {
+                    success_response(json!(variant))
+                } else {
+                    error_response(Status::InternalServerError, "Failed to delete variant")
+                }
+            }
+            Err(_) => error_response(Status::NotFound, "Variant not found"),
+        }
+    })
+}
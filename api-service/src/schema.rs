@@ -1,3 +1,170 @@
+table! {
+    item_alias (id) {
+        id -> Int4,
+        item_id -> Int4,
+        alias -> Varchar,
+    }
+}
+
+table! {
+    item_barcode (gtin) {
+        gtin -> Varchar,
+        item_id -> Int4,
+        variant_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    item_nutrition (item_id) {
+        item_id -> Int4,
+        energy_kcal -> Nullable<Float8>,
+        protein_g -> Nullable<Float8>,
+        fat_g -> Nullable<Float8>,
+        carbohydrate_g -> Nullable<Float8>,
+        sugar_g -> Nullable<Float8>,
+        salt_g -> Nullable<Float8>,
+        fibre_g -> Nullable<Float8>,
+        unit_weight_g -> Nullable<Float8>,
+        vegan -> Nullable<Bool>,
+        gluten_free -> Nullable<Bool>,
+        contains_nuts -> Nullable<Bool>,
+    }
+}
+
+table! {
+    item_price (id) {
+        id -> Int4,
+        item_id -> Int4,
+        store_id -> Int4,
+        reporter_id -> Int4,
+        price_cents -> Int4,
+        currency -> Varchar,
+        quantity -> Float8,
+        unit -> Varchar,
+        observed_at -> Timestamp,
+        variant_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    item_proposal (id) {
+        id -> Int4,
+        proposed_by -> Int4,
+        item_id -> Nullable<Int4>,
+        name -> Varchar,
+        description -> Nullable<Varchar>,
+        image_url -> Nullable<Varchar>,
+        default_unit_type -> Varchar,
+        status -> Varchar,
+        rejection_reason -> Nullable<Varchar>,
+        resolved_item_id -> Nullable<Int4>,
+        created_at -> Timestamp,
+        reviewed_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    item_translation (item_id, locale) {
+        item_id -> Int4,
+        locale -> Varchar,
+        name -> Varchar,
+    }
+}
+
+table! {
+    item_variant (id) {
+        id -> Int4,
+        item_id -> Int4,
+        brand -> Varchar,
+        package_quantity -> Float8,
+        package_unit -> Varchar,
+        image_url -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    list_template (id) {
+        id -> Int4,
+        name -> Varchar,
+        created_by -> Int4,
+    }
+}
+
+table! {
+    list_template_item (id) {
+        id -> Int4,
+        template_id -> Int4,
+        item_id -> Int4,
+        quantity -> Float8,
+        unit -> Varchar,
+    }
+}
+
+table! {
+    meal_plan_entry (id) {
+        id -> Int4,
+        user_id -> Int4,
+        planned_for -> Date,
+        meal_slot -> Varchar,
+        recipe_id -> Int4,
+        servings -> Int4,
+    }
+}
+
+table! {
+    notification (id) {
+        id -> Int4,
+        user_id -> Int4,
+        kind -> Varchar,
+        message -> Varchar,
+        pantry_item_id -> Nullable<Int4>,
+        is_read -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    pantry_item (id) {
+        id -> Int4,
+        user_id -> Int4,
+        item_id -> Int4,
+        quantity -> Float8,
+        unit -> Varchar,
+        location -> Varchar,
+        expiry_date -> Nullable<Date>,
+    }
+}
+
+table! {
+    pantry_threshold (user_id, item_id) {
+        user_id -> Int4,
+        item_id -> Int4,
+        quantity -> Float8,
+        unit -> Varchar,
+    }
+}
+
+table! {
+    recipe (id) {
+        id -> Int4,
+        name -> Varchar,
+        description -> Nullable<Varchar>,
+        servings -> Int4,
+        steps -> Array<Text>,
+        created_by -> Int4,
+    }
+}
+
+table! {
+    recipe_ingredient (id) {
+        id -> Int4,
+        recipe_id -> Int4,
+        item_id -> Int4,
+        quantity -> Float8,
+        unit -> Varchar,
+    }
+}
+
 table! {
     shopping_item (id) {
         id -> Int4,
@@ -5,6 +172,7 @@ table! {
         description -> Nullable<Varchar>,
         image_url -> Nullable<Varchar>,
         default_unit_type -> Varchar,
+        owner_id -> Nullable<Int4>,
     }
 }
 
@@ -18,6 +186,25 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::models::store::Geography;
+
+    store (id) {
+        id -> Int4,
+        name -> Varchar,
+        address -> Nullable<Varchar>,
+        location -> Geography,
+    }
+}
+
+table! {
+    user_dietary_restriction (user_id, restriction) {
+        user_id -> Int4,
+        restriction -> Varchar,
+    }
+}
+
 table! {
     users (id) {
         id -> Int4,
@@ -28,8 +215,53 @@ table! {
     }
 }
 
+joinable!(item_alias -> shopping_item (item_id));
+joinable!(item_barcode -> item_variant (variant_id));
+joinable!(item_barcode -> shopping_item (item_id));
+joinable!(item_nutrition -> shopping_item (item_id));
+joinable!(item_price -> item_variant (variant_id));
+joinable!(item_price -> shopping_item (item_id));
+joinable!(item_price -> store (store_id));
+joinable!(item_price -> users (reporter_id));
+joinable!(item_proposal -> users (proposed_by));
+joinable!(item_translation -> shopping_item (item_id));
+joinable!(item_variant -> shopping_item (item_id));
+joinable!(list_template -> users (created_by));
+joinable!(list_template_item -> list_template (template_id));
+joinable!(list_template_item -> shopping_item (item_id));
+joinable!(meal_plan_entry -> recipe (recipe_id));
+joinable!(meal_plan_entry -> users (user_id));
+joinable!(notification -> pantry_item (pantry_item_id));
+joinable!(notification -> users (user_id));
+joinable!(pantry_item -> shopping_item (item_id));
+joinable!(pantry_item -> users (user_id));
+joinable!(pantry_threshold -> shopping_item (item_id));
+joinable!(pantry_threshold -> users (user_id));
+joinable!(recipe -> users (created_by));
+joinable!(recipe_ingredient -> recipe (recipe_id));
+joinable!(recipe_ingredient -> shopping_item (item_id));
+joinable!(shopping_item -> users (owner_id));
+joinable!(user_dietary_restriction -> users (user_id));
+
 allow_tables_to_appear_in_same_query!(
+    item_alias,
+    item_barcode,
+    item_nutrition,
+    item_price,
+    item_proposal,
+    item_translation,
+    item_variant,
+    list_template,
+    list_template_item,
+    meal_plan_entry,
+    notification,
+    pantry_item,
+    pantry_threshold,
+    recipe,
+    recipe_ingredient,
     shopping_item,
     spatial_ref_sys,
+    store,
+    user_dietary_restriction,
     users,
 );
@@ -0,0 +1,277 @@
+use diesel::pg::PgConnection;
+use serde_derive::Serialize;
+
+use crate::models::alias::ItemAlias;
+use crate::models::item::{ItemVisibility, ShoppingItem};
+use crate::models::unit::{Quantity, Unit};
+
+/// A line of free-form text split into the quantity it mentions and the item name left
+/// over.
+#[derive(Debug, Serialize)]
+pub struct ParsedLine {
+    pub line: String,
+    pub name: String,
+    pub quantity: Quantity,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchedLine {
+    pub line: String,
+    pub item: ShoppingItem,
+    pub quantity: Quantity,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnmatchedLine {
+    pub line: String,
+    pub name: String,
+    pub quantity: Quantity,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TextImport {
+    pub matched: Vec<MatchedLine>,
+    pub unmatched: Vec<UnmatchedLine>,
+}
+
+/// Splits a token such as `2`, `1.5`, `1/2` or `2kg` into its amount and whatever
+/// follows the number.
+fn split_amount(token: &str) -> Option<(f64, &str)> {
+    let end = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '/'))
+        .unwrap_or(token.len());
+    let (number, rest) = token.split_at(end);
+    let amount = match number.split_once('/') {
+        Some((numerator, denominator)) => {
+            numerator.parse::<f64>().ok()? / denominator.parse::<f64>().ok()?
+        }
+        None => number.parse::<f64>().ok()?,
+    };
+    if amount.is_finite() && amount > 0.0 {
+        Some((amount, rest))
+    } else {
+        None
+    }
+}
+
+/// Reads a count written as `x12`, `12x` or `×12`.
+fn parse_multiplier(token: &str) -> Option<f64> {
+    let lowered = token.to_lowercase();
+    let digits = lowered
+        .strip_prefix('x')
+        .or_else(|| lowered.strip_prefix('×'))
+        .or_else(|| lowered.strip_suffix('x'))?;
+    match split_amount(digits) {
+        Some((amount, "")) => Some(amount),
+        _ => None,
+    }
+}
+
+/// Reads a quantity from the start of `tokens`, returning it with the number of tokens
+/// it used.
+fn leading_quantity(tokens: &[&str]) -> Option<(Quantity, usize)> {
+    let (amount, rest) = split_amount(tokens.first()?)?;
+    if !rest.is_empty() {
+        let unit = Unit::from_symbol(rest)?;
+        return Some((Quantity::new(amount, unit), 1));
+    }
+    match tokens.get(1) {
+        Some(token) if *token == "x" || *token == "×" => {
+            Some((Quantity::new(amount, Unit::Each), 2))
+        }
+        Some(token) => match Unit::from_symbol(token) {
+            Some(unit) => Some((Quantity::new(amount, unit), 2)),
+            None => Some((Quantity::new(amount, Unit::Each), 1)),
+        },
+        None => Some((Quantity::new(amount, Unit::Each), 1)),
+    }
+}
+
+/// Reads a quantity from the end of `tokens`, returning it with the number of tokens it
+/// used.
+fn trailing_quantity(tokens: &[&str]) -> Option<(Quantity, usize)> {
+    let last = tokens.last()?;
+    if let Some(amount) = parse_multiplier(last) {
+        return Some((Quantity::new(amount, Unit::Each), 1));
+    }
+    if let Some((amount, rest)) = split_amount(last) {
+        if !rest.is_empty() {
+            let unit = Unit::from_symbol(rest)?;
+            return Some((Quantity::new(amount, unit), 1));
+        }
+    }
+    let unit = Unit::from_symbol(last)?;
+    match split_amount(tokens.get(tokens.len().checked_sub(2)?)?) {
+        Some((amount, "")) => Some((Quantity::new(amount, unit), 2)),
+        _ => None,
+    }
+}
+
+/// Parses a single line such as `2kg potatoes`, `1 L milk` or `eggs x12`. Lines without a
+/// recognisable quantity default to one of the item. Returns `None` for blank lines.
+pub fn parse_line(line: &str) -> Option<ParsedLine> {
+    let line = line
+        .trim()
+        .trim_start_matches(|c| c == '-' || c == '*')
+        .trim();
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let (quantity, name_tokens) = match leading_quantity(&tokens) {
+        Some((quantity, used)) if used < tokens.len() => (quantity, &tokens[used..]),
+        _ => match trailing_quantity(&tokens) {
+            Some((quantity, used)) if used < tokens.len() => {
+                (quantity, &tokens[..tokens.len() - used])
+            }
+            _ => (Quantity::new(1.0, Unit::Each), &tokens[..]),
+        },
+    };
+    let name_tokens = match name_tokens.first() {
+        Some(&"of") if name_tokens.len() > 1 => &name_tokens[1..],
+        _ => name_tokens,
+    };
+
+    Some(ParsedLine {
+        line: String::from(line),
+        name: name_tokens.join(" "),
+        quantity,
+    })
+}
+
+/// Splits pasted text into entries, one per line or comma-separated item.
+pub fn parse_text(text: &str) -> Vec<ParsedLine> {
+    text.split(|c| c == '\n' || c == ',' || c == ';')
+        .filter_map(parse_line)
+        .collect()
+}
+
+/// Candidate search terms for a name, falling back to simple singular forms so that
+/// "potatoes" finds "Potato".
+fn search_terms(name: &str) -> Vec<&str> {
+    let mut terms = vec![name];
+    for suffix in ["es", "s"] {
+        if let Some(stem) = name.strip_suffix(suffix) {
+            if stem.len() > 1 {
+                terms.push(stem);
+            }
+        }
+    }
+    terms
+}
+
+/// Picks the catalog item for a name, preferring an exact (case insensitive) match on
+/// the item name or one of its aliases and otherwise the shortest item name containing
+/// it.
+pub fn match_item(
+    conn: &PgConnection,
+    name: &str,
+    visibility: ItemVisibility,
+) -> Option<ShoppingItem> {
+    for term in search_terms(name) {
+        let mut candidates = ShoppingItem::search_all_items(conn, term, visibility);
+        let alias_item_id = ItemAlias::get_alias_by_name(conn, term)
+            .ok()
+            .map(|alias| alias.item_id);
+        if let Some(index) = candidates
+            .iter()
+            .position(|item| item.name.eq_ignore_ascii_case(term) || Some(item.id) == alias_item_id)
+        {
+            return Some(candidates.swap_remove(index));
+        }
+        candidates.sort_by_key(|item| item.name.len());
+        if let Some(item) = candidates.into_iter().next() {
+            return Some(item);
+        }
+    }
+    None
+}
+
+/// Parses pasted text and resolves each entry against the catalog. Entries that match no
+/// item, or whose unit the matched item cannot be measured in, are returned separately
+/// for the user to resolve.
+pub fn import_text(conn: &PgConnection, text: &str, visibility: ItemVisibility) -> TextImport {
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    for parsed in parse_text(text) {
+        let item = match match_item(conn, &parsed.name, visibility) {
+            Some(item) => item,
+            None => {
+                unmatched.push(UnmatchedLine {
+                    line: parsed.line,
+                    name: parsed.name,
+                    quantity: parsed.quantity,
+                    reason: String::from("No matching item"),
+                });
+                continue;
+            }
+        };
+        match item.check_quantity(&parsed.quantity) {
+            Ok(()) => matched.push(MatchedLine {
+                line: parsed.line,
+                item,
+                quantity: parsed.quantity,
+            }),
+            Err(err) => unmatched.push(UnmatchedLine {
+                line: parsed.line,
+                name: parsed.name,
+                quantity: parsed.quantity,
+                reason: err.to_string(),
+            }),
+        }
+    }
+    TextImport { matched, unmatched }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_parsed(line: &str, name: &str, amount: f64, unit: Unit) {
+        let parsed = parse_line(line).unwrap();
+        assert_eq!(parsed.name, name);
+        assert_eq!(parsed.quantity.unit, unit);
+        assert!((parsed.quantity.amount - amount).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parses_amount_joined_to_unit() {
+        assert_parsed("2kg potatoes", "potatoes", 2.0, Unit::Kilogram);
+    }
+
+    #[test]
+    fn parses_amount_and_unit_as_separate_words() {
+        assert_parsed("1 L milk", "milk", 1.0, Unit::Litre);
+    }
+
+    #[test]
+    fn parses_trailing_multiplier() {
+        assert_parsed("eggs x12", "eggs", 12.0, Unit::Each);
+    }
+
+    #[test]
+    fn defaults_to_one_when_no_quantity_is_given() {
+        assert_parsed("bread", "bread", 1.0, Unit::Each);
+        assert_parsed("- sourdough bread", "sourdough bread", 1.0, Unit::Each);
+    }
+
+    #[test]
+    fn treats_an_unknown_unit_as_part_of_the_name() {
+        assert_parsed("2 bunches parsley", "bunches parsley", 2.0, Unit::Each);
+        assert_parsed("3zz apples", "3zz apples", 1.0, Unit::Each);
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        assert!(parse_line("   ").is_none());
+    }
+
+    #[test]
+    fn splits_text_on_lines_and_commas() {
+        let parsed = parse_text("2kg potatoes, 1 L milk\neggs x12;\n\nbread");
+        let names: Vec<&str> = parsed.iter().map(|line| line.name.as_str()).collect();
+        assert_eq!(names, vec!["potatoes", "milk", "eggs", "bread"]);
+    }
+}
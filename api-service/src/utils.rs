@@ -1,3 +1,5 @@
+use chrono::NaiveDate;
+
 use crate::responses::JsonResponse;
 
 pub fn handle_request<T, F>(request: Result<T, JsonResponse>, handler: F) -> JsonResponse
@@ -9,3 +11,7 @@ where
         Err(err) => err,
     }
 }
+
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}